@@ -1,35 +1,50 @@
+use std::path::Path;
+
 use crate::args::Args;
 use crate::colorscheme::Colorscheme;
+use crate::units::{Units, UnitsArg};
 use crate::widgets::*;
 
-pub struct App<'a, 'b> {
+pub struct App<'a> {
 	pub help_menu: HelpMenu<'a>,
 	pub statusbar: Option<Statusbar<'a>>,
-	pub widgets: Widgets<'a, 'b>,
+	pub widgets: Widgets<'a>,
 }
 
-pub struct Widgets<'a, 'b> {
+pub struct Widgets<'a> {
 	pub battery: Option<BatteryWidget<'a>>,
+	pub connections: Option<ConnectionsWidget<'a>>,
 	pub cpu: CpuWidget<'a>,
 	pub disk: Option<DiskWidget<'a>>,
 	pub mem: MemWidget<'a>,
-	pub net: Option<NetWidget<'a, 'b>>,
+	pub net: Option<NetWidget<'a>>,
 	pub proc: ProcWidget<'a>,
+	pub protocols: Option<ProtocolsWidget<'a>>,
+	pub psi: Option<PsiWidget<'a>>,
+	pub raid: Option<RaidWidget<'a>>,
 	pub temp: Option<TempWidget<'a>>,
 }
 
-pub fn setup_app<'a, 'b>(
-	args: &'b Args,
+// Where the Net widget keeps its data usage, in the state directory along with the error log.
+const NET_USAGE_FILE: &str = "net_usage.json";
+
+pub fn setup_app<'a>(
+	args: &Args,
 	colorscheme: &'a Colorscheme,
 	program_name: &str,
-) -> App<'a, 'b> {
+	state_dir: &Path,
+) -> App<'a> {
 	let cpu = CpuWidget::new(colorscheme, args.interval, args.average_cpu, args.per_cpu);
-	let mem = MemWidget::new(colorscheme, args.interval);
+	// Each widget's units fall back to the global ones for anything they leave out.
+	let units = args.units.or(Units::default());
+	let widget_units = |widget_units: Option<UnitsArg>| widget_units.unwrap_or_default().or(units);
+
+	let mem = MemWidget::new(colorscheme, widget_units(args.mem_units), args.interval);
 	let proc = ProcWidget::new(colorscheme);
 	let help_menu = HelpMenu::new(colorscheme);
 
-	let (battery, disk, net, temp) = if args.minimal {
-		(None, None, None, None)
+	let (battery, connections, disk, net, protocols, psi, raid, temp) = if args.minimal {
+		(None, None, None, None, None, None, None, None)
 	} else {
 		(
 			if args.battery {
@@ -37,8 +52,52 @@ pub fn setup_app<'a, 'b>(
 			} else {
 				None
 			},
-			Some(DiskWidget::new(colorscheme)),
-			Some(NetWidget::new(colorscheme, &args.interface)),
+			if args.connections {
+				Some(ConnectionsWidget::new(colorscheme))
+			} else {
+				None
+			},
+			Some(DiskWidget::new(
+				colorscheme,
+				widget_units(args.disk_units),
+				args.all_filesystems,
+				args.mount_include.clone(),
+				args.mount_exclude.clone(),
+				args.fill_window,
+			)),
+			Some(NetWidget::new(
+				colorscheme,
+				widget_units(args.net_units),
+				&args.interface,
+				args.interface_include.clone(),
+				args.interface_exclude.clone(),
+				if args.net_usage || args.quota.is_some() {
+					Some(UsageTracker::load(
+						state_dir.join(NET_USAGE_FILE),
+						args.quota.map(|quota| Quota {
+							bytes: quota.0,
+							warning_percent: args.quota_warning,
+						}),
+					))
+				} else {
+					None
+				},
+			)),
+			if args.net_protocols {
+				Some(ProtocolsWidget::new(colorscheme))
+			} else {
+				None
+			},
+			if !args.no_psi && PsiWidget::is_supported() {
+				Some(PsiWidget::new(colorscheme, args.interval))
+			} else {
+				None
+			},
+			if RaidWidget::has_arrays() {
+				Some(RaidWidget::new(colorscheme, units))
+			} else {
+				None
+			},
 			Some(TempWidget::new(colorscheme, args.fahrenheit)),
 		)
 	};
@@ -54,11 +113,15 @@ pub fn setup_app<'a, 'b>(
 		statusbar,
 		widgets: Widgets {
 			battery,
+			connections,
 			cpu,
 			disk,
 			mem,
 			net,
 			proc,
+			protocols,
+			psi,
+			raid,
 			temp,
 		},
 	}
@@ -1,10 +1,16 @@
+use glob::Pattern;
 use num_rational::Ratio;
 use structopt::StructOpt;
 
 use crate::colorscheme::Colorschemes;
+use crate::units::{ByteSize, UnitsArg};
 
 #[derive(StructOpt)]
 pub struct Args {
+	/// Show all filesystems in the Disk widget, including virtual filesystems like tmpfs. Network and FUSE filesystems are only shown when they match '--mount-include', since they can hang.
+	#[structopt(long = "all-filesystems")]
+	pub all_filesystems: bool,
+
 	/// Show average CPU in the CPU widget.
 	#[structopt(short = "a", long = "average-cpu")]
 	pub average_cpu: bool,
@@ -28,27 +34,87 @@ pub struct Args {
 	)]
 	pub colorscheme: Colorschemes,
 
+	/// Show Network Connections widget with the TCP and UDP sockets and the processes that own them (overridden by 'minimal' flag).
+	#[structopt(long = "connections")]
+	pub connections: bool,
+
+	/// Units for the Disk widget, overriding '--units'.
+	#[structopt(long = "disk-units")]
+	pub disk_units: Option<UnitsArg>,
+
 	/// Show temperatures in fahrenheit.
 	#[structopt(short = "f", long = "fahrenheit")]
 	pub fahrenheit: bool,
 
+	/// Window in seconds over which the Disk widget projects how long until each filesystem is full.
+	#[structopt(long = "fill-window", default_value = "600")]
+	pub fill_window: u64,
+
 	/// The name of the network interface to show in the Net widget. 'all' shows all interfaces.
 	#[structopt(short = "i", long = "interface", default_value = "all")]
 	pub interface: String,
 
+	/// Leave interfaces matching any of these glob patterns out of the 'all' total in the Net widget, e.g. 'docker*' 'veth*'. The tun0 VPN interface is always left out unless it matches '--interface-include'.
+	#[structopt(long = "interface-exclude")]
+	pub interface_exclude: Vec<Pattern>,
+
+	/// Only count interfaces matching one of these glob patterns in the 'all' total in the Net widget.
+	#[structopt(long = "interface-include")]
+	pub interface_include: Vec<Pattern>,
+
 	/// Interval in seconds between updates of the CPU and Mem widgets. Can specify either a whole number or a fraction with a numerator of 1.
 	#[structopt(short = "I", long = "interval", default_value = "1")]
 	pub interval: Ratio<u64>,
 
+	/// Units for the Mem widget, overriding '--units'.
+	#[structopt(long = "mem-units")]
+	pub mem_units: Option<UnitsArg>,
+
 	/// Only show the CPU, Mem, and Process widgets.
 	#[structopt(short = "m", long = "minimal")]
 	pub minimal: bool,
 
+	/// Hide filesystems whose mountpoint matches any of these glob patterns in the Disk widget, e.g. '/snap/*'.
+	#[structopt(long = "mount-exclude")]
+	pub mount_exclude: Vec<Pattern>,
+
+	/// Only show filesystems whose mountpoint matches one of these glob patterns in the Disk widget.
+	#[structopt(long = "mount-include")]
+	pub mount_include: Vec<Pattern>,
+
+	/// Show Network Protocols widget with TCP and UDP counters like retransmits and buffer drops (overridden by 'minimal' flag).
+	#[structopt(long = "net-protocols")]
+	pub net_protocols: bool,
+
+	/// Units for the Net widget, overriding '--units', e.g. 'bits,si' for Mbit/s.
+	#[structopt(long = "net-units")]
+	pub net_units: Option<UnitsArg>,
+
+	/// Keep daily and monthly totals of the data sent and received by each interface in the Net widget, saved across restarts.
+	#[structopt(long = "net-usage")]
+	pub net_usage: bool,
+
+	/// Hide the Pressure Stall Information widget, which is shown when the kernel supports it.
+	#[structopt(long = "no-psi")]
+	pub no_psi: bool,
+
 	/// Show each CPU in the CPU widget.
 	#[structopt(short = "p", long = "per-cpu")]
 	pub per_cpu: bool,
 
+	/// Monthly data quota for the interfaces shown in the Net widget, e.g. '50GiB' or '500GB'. Implies '--net-usage'.
+	#[structopt(long = "quota")]
+	pub quota: Option<ByteSize>,
+
+	/// Percent of the '--quota' used before the Net widget warns about it.
+	#[structopt(long = "quota-warning", default_value = "90")]
+	pub quota_warning: f64,
+
 	/// Show a statusbar with the time.
 	#[structopt(short = "s", long = "statusbar")]
 	pub statusbar: bool,
+
+	/// Show sizes and rates in 'bits' or 'bytes' with 'si' (kB, MB) or 'iec' (KiB, MiB) prefixes, e.g. 'bits,si'.
+	#[structopt(short = "u", long = "units", default_value = "bytes,iec")]
+	pub units: UnitsArg,
 }
@@ -4,6 +4,10 @@ use tui::{Frame, Terminal};
 
 use crate::app::{App, Widgets};
 
+// The Net widget keeps room for its borders and a line of traffic when other widgets share its
+// column.
+const NET_MIN_HEIGHT: u16 = 3;
+
 pub fn draw<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) {
 	terminal
 		.draw(|mut frame| {
@@ -50,20 +54,27 @@ pub fn draw_widgets<B: Backend>(frame: &mut Frame<B>, widgets: &mut Widgets, are
 }
 
 pub fn draw_top_row<B: Backend>(frame: &mut Frame<B>, widgets: &mut Widgets, area: Rect) {
+	let constraints = match (widgets.battery.is_some(), widgets.psi.is_some()) {
+		(true, true) => vec![
+			Constraint::Ratio(1, 4),
+			Constraint::Ratio(1, 4),
+			Constraint::Ratio(1, 2),
+		],
+		(true, false) | (false, true) => vec![Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)],
+		(false, false) => vec![Constraint::Percentage(100)],
+	};
+	let horizontal_chunks = Layout::default()
+		.direction(Direction::Horizontal)
+		.constraints(constraints)
+		.split(area);
+	let mut chunks = horizontal_chunks.into_iter();
 	if let Some(battery) = widgets.battery.as_ref() {
-		let horizontal_chunks = Layout::default()
-			.direction(Direction::Horizontal)
-			.constraints([Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)].as_ref())
-			.split(area);
-		frame.render_widget(battery, horizontal_chunks[0]);
-		frame.render_widget(&widgets.cpu, horizontal_chunks[1]);
-	} else {
-		let horizontal_chunks = Layout::default()
-			.direction(Direction::Horizontal)
-			.constraints([Constraint::Percentage(100)].as_ref())
-			.split(area);
-		frame.render_widget(&widgets.cpu, horizontal_chunks[0]);
+		frame.render_widget(battery, chunks.next().unwrap());
+	}
+	if let Some(psi) = widgets.psi.as_ref() {
+		frame.render_widget(psi, chunks.next().unwrap());
 	}
+	frame.render_widget(&mut widgets.cpu, chunks.next().unwrap());
 }
 
 pub fn draw_middle_row<B: Backend>(frame: &mut Frame<B>, widgets: &mut Widgets, area: Rect) {
@@ -76,8 +87,18 @@ pub fn draw_middle_row<B: Backend>(frame: &mut Frame<B>, widgets: &mut Widgets,
 		.direction(Direction::Vertical)
 		.constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref())
 		.split(horizontal_chunks[0]);
-	frame.render_widget(widgets.disk.as_ref().unwrap(), vertical_chunks[0]);
-	frame.render_widget(widgets.temp.as_ref().unwrap(), vertical_chunks[1]);
+	frame.render_widget(widgets.disk.as_mut().unwrap(), vertical_chunks[0]);
+	if let Some(raid) = widgets.raid.as_ref() {
+		// The RAID widget takes what it needs from the temperatures.
+		let raid_chunks = Layout::default()
+			.direction(Direction::Vertical)
+			.constraints([Constraint::Length(raid.get_height()), Constraint::Min(0)].as_ref())
+			.split(vertical_chunks[1]);
+		frame.render_widget(raid, raid_chunks[0]);
+		frame.render_widget(widgets.temp.as_ref().unwrap(), raid_chunks[1]);
+	} else {
+		frame.render_widget(widgets.temp.as_ref().unwrap(), vertical_chunks[1]);
+	}
 }
 
 pub fn draw_bottom_row<B: Backend>(frame: &mut Frame<B>, widgets: &mut Widgets, area: Rect) {
@@ -86,7 +107,46 @@ pub fn draw_bottom_row<B: Backend>(frame: &mut Frame<B>, widgets: &mut Widgets,
 		.constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref())
 		.split(area);
 	if let Some(net) = widgets.net.as_ref() {
-		frame.render_widget(net, horizontal_chunks[0]);
+		let mut net_area = horizontal_chunks[0];
+		if let Some(protocols) = widgets.protocols.as_ref() {
+			// The protocol counters take what they need from the bottom, up to half of the widget,
+			// and leave out the metrics that don't fit.
+			let protocols_height = u16::min(
+				u16::min(protocols.get_height(), net_area.height / 2),
+				net_area.height.saturating_sub(NET_MIN_HEIGHT),
+			);
+			let vertical_chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[
+						Constraint::Min(NET_MIN_HEIGHT),
+						Constraint::Length(protocols_height),
+					]
+					.as_ref(),
+				)
+				.split(net_area);
+			net_area = vertical_chunks[0];
+			frame.render_widget(protocols, vertical_chunks[1]);
+		}
+		if let Some(connections) = widgets.connections.as_ref() {
+			let connections_height = u16::min(
+				net_area.height / 2,
+				net_area.height.saturating_sub(NET_MIN_HEIGHT),
+			);
+			let vertical_chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[
+						Constraint::Min(NET_MIN_HEIGHT),
+						Constraint::Length(connections_height),
+					]
+					.as_ref(),
+				)
+				.split(net_area);
+			net_area = vertical_chunks[0];
+			frame.render_widget(connections, vertical_chunks[1]);
+		}
+		frame.render_widget(net, net_area);
 	} else {
 		frame.render_widget(&widgets.mem, horizontal_chunks[0]);
 	}
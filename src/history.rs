@@ -0,0 +1,149 @@
+use std::cmp::Ordering;
+
+/// How many updates the graphs can be scaled out to show.
+pub const MAX_HORIZONTAL_SCALE: u64 = 600;
+
+// Enough entries to fill a graph at its largest scale, plus one more so that the line still reaches
+// the left edge of the graph.
+const DEFAULT_CAPACITY: usize = MAX_HORIZONTAL_SCALE as usize + 2;
+
+/// A fixed-capacity time series that forgets its oldest entries once it's full.
+///
+/// Entries are stored in a `Vec` that is only compacted after it grows to twice the capacity, so
+/// that the retained entries can always be borrowed as a single slice in chronological order.
+pub struct History<T> {
+	capacity: usize,
+	data: Vec<T>,
+}
+
+impl<T> History<T> {
+	pub fn new(capacity: usize) -> History<T> {
+		History {
+			capacity,
+			data: Vec::with_capacity(capacity * 2),
+		}
+	}
+
+	pub fn push(&mut self, entry: T) {
+		if self.data.len() >= self.capacity * 2 {
+			self.data.drain(..self.data.len() - self.capacity + 1);
+		}
+		self.data.push(entry);
+	}
+
+	pub fn as_slice(&self) -> &[T] {
+		&self.data[self.data.len().saturating_sub(self.capacity)..]
+	}
+
+	pub fn last(&self) -> Option<&T> {
+		self.data.last()
+	}
+}
+
+impl<T> Default for History<T> {
+	fn default() -> History<T> {
+		History::new(DEFAULT_CAPACITY)
+	}
+}
+
+/// Summary of the values in a window of a time series.
+pub struct Stats {
+	pub min: f64,
+	pub max: f64,
+	pub mean: f64,
+	pub p95: f64,
+}
+
+impl Stats {
+	/// Returns `None` if there are no values.
+	pub fn new<I: IntoIterator<Item = f64>>(values: I) -> Option<Stats> {
+		let mut values: Vec<f64> = values.into_iter().collect();
+		if values.is_empty() {
+			return None;
+		}
+		values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+		let len = values.len();
+		// nearest-rank percentile
+		let p95_index = ((len as f64 * 0.95).ceil() as usize).saturating_sub(1);
+		Some(Stats {
+			min: values[0],
+			max: values[len - 1],
+			mean: values.iter().sum::<f64>() / len as f64,
+			p95: values[p95_index],
+		})
+	}
+
+	/// Summarizes the points of a graph that are at or to the right of `min_x`.
+	pub fn of_window(points: &[(f64, f64)], min_x: f64) -> Option<Stats> {
+		Stats::new(
+			points
+				.iter()
+				.filter(|(x, _y)| *x >= min_x)
+				.map(|(_x, y)| *y),
+		)
+	}
+}
+
+/// The slope of the least squares line through the points, or `None` if there are fewer than two
+/// distinct x values.
+pub fn linear_trend(points: &[(f64, f64)]) -> Option<f64> {
+	let len = points.len() as f64;
+	let mean_x = points.iter().map(|(x, _y)| x).sum::<f64>() / len;
+	let mean_y = points.iter().map(|(_x, y)| y).sum::<f64>() / len;
+	let covariance: f64 = points
+		.iter()
+		.map(|(x, y)| (x - mean_x) * (y - mean_y))
+		.sum();
+	let variance: f64 = points.iter().map(|(x, _y)| (x - mean_x).powi(2)).sum();
+	if variance > 0.0 {
+		Some(covariance / variance)
+	} else {
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn it_keeps_the_most_recent_entries() {
+		let mut history = History::new(3);
+		for i in 0..10 {
+			history.push(i);
+		}
+		assert_eq!(history.as_slice(), &[7, 8, 9]);
+		assert_eq!(history.last(), Some(&9));
+		assert!(history.data.len() <= 6);
+	}
+
+	#[test]
+	fn it_summarizes_a_window() {
+		let points: Vec<(f64, f64)> = (0..=100).map(|i| (i as f64, i as f64)).collect();
+		let stats = Stats::of_window(&points, 1.0).unwrap();
+		assert_eq!(stats.min, 1.0);
+		assert_eq!(stats.max, 100.0);
+		assert_eq!(stats.mean, 50.5);
+		assert_eq!(stats.p95, 95.0);
+		assert!(Stats::of_window(&points, 101.0).is_none());
+	}
+
+	#[test]
+	fn it_fits_a_trend() {
+		let points: Vec<(f64, f64)> = (0..10)
+			.map(|i| (i as f64, 100.0 - 2.0 * i as f64))
+			.collect();
+		assert_eq!(linear_trend(&points), Some(-2.0));
+		assert_eq!(linear_trend(&points[..1]), None);
+		assert_eq!(linear_trend(&[]), None);
+	}
+
+	#[test]
+	fn it_returns_all_entries_before_it_is_full() {
+		let mut history = History::new(3);
+		history.push(1);
+		history.push(2);
+		assert_eq!(history.as_slice(), &[1, 2]);
+	}
+}
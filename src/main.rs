@@ -2,12 +2,15 @@ mod app;
 mod args;
 mod colorscheme;
 mod draw;
+mod history;
 mod sparkline;
+mod units;
 mod update;
 mod widgets;
 
 use std::fs;
 use std::io::{self, Write};
+use std::iter;
 use std::panic;
 use std::path::Path;
 use std::thread;
@@ -15,11 +18,12 @@ use std::time::Duration;
 
 use crossbeam_channel::{select, tick, unbounded, Receiver};
 use crossterm::cursor;
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent};
 use crossterm::execute;
 use crossterm::terminal;
 use num_rational::Ratio;
 use platform_dirs::{AppDirs, AppUI};
+use signal_hook::iterator::Signals;
 use structopt::StructOpt;
 use tui::backend::CrosstermBackend;
 use tui::Terminal;
@@ -29,6 +33,7 @@ use args::*;
 use colorscheme::*;
 use draw::*;
 use update::*;
+use widgets::ScrollableTable;
 
 const PROGRAM_NAME: &str = env!("CARGO_PKG_NAME");
 
@@ -37,6 +42,7 @@ fn setup_terminal() {
 
 	execute!(stdout, terminal::EnterAlternateScreen).unwrap();
 	execute!(stdout, cursor::Hide).unwrap();
+	execute!(stdout, event::EnableMouseCapture).unwrap();
 
 	// Needed for when ytop is run in a TTY since TTYs don't actually have an alternate screen.
 	// Must be executed after attempting to enter the alternate screen so that it only clears the
@@ -59,6 +65,7 @@ fn cleanup_terminal() {
 	execute!(stdout, cursor::MoveTo(0, 0)).unwrap();
 	execute!(stdout, terminal::Clear(terminal::ClearType::All)).unwrap();
 
+	execute!(stdout, event::DisableMouseCapture).unwrap();
 	execute!(stdout, terminal::LeaveAlternateScreen).unwrap();
 	execute!(stdout, cursor::Show).unwrap();
 
@@ -74,18 +81,29 @@ fn setup_ui_events() -> Receiver<Event> {
 	receiver
 }
 
+// Handles SIGINT and SIGTERM, and SIGHUP for when the terminal is closed, so that we can save the
+// data usage before exiting.
 fn setup_ctrl_c() -> Receiver<()> {
 	let (sender, receiver) = unbounded();
+	let hangup_sender = sender.clone();
 	ctrlc::set_handler(move || {
 		sender.send(()).unwrap();
 	})
 	.unwrap();
 
+	let hangup_signals = Signals::new(iter::once(signal_hook::SIGHUP)).unwrap();
+	thread::spawn(move || {
+		for _ in hangup_signals.forever() {
+			hangup_sender.send(()).unwrap();
+		}
+	});
+
 	receiver
 }
 
-// The log file currently isn't being used for anything right now, but it does help when debugging
-// and we'll probably use it when we clean up the error handling.
+// The log file is only used for errors we can recover from, like failing to save the data usage,
+// but it does help when debugging and we'll probably use it more when we clean up the error
+// handling.
 fn setup_logfile(logfile_path: &Path) {
 	fs::create_dir_all(logfile_path.parent().unwrap()).unwrap();
 	let logfile = fs::OpenOptions::new()
@@ -119,6 +137,15 @@ fn setup_panic_hook() {
 	}));
 }
 
+// The navigation keys scroll the disk table while it's focused and the process table otherwise.
+// Also returns whether the table is drawn along with the graphs rather than on its own.
+fn focused_table<'a>(widgets: &'a mut Widgets) -> (&'a mut dyn ScrollableTable, bool) {
+	match widgets.disk.as_mut().filter(|disk| disk.is_focused()) {
+		Some(disk) => (disk, true),
+		None => (&mut widgets.proc, false),
+	}
+}
+
 fn main() {
 	better_panic::install();
 
@@ -129,7 +156,7 @@ fn main() {
 	let logfile_path = app_dirs.state_dir.join("errors.log");
 
 	let colorscheme = read_colorscheme(&app_dirs.config_dir, &args.colorscheme);
-	let mut app = setup_app(&args, &colorscheme, PROGRAM_NAME);
+	let mut app = setup_app(&args, &colorscheme, PROGRAM_NAME, &app_dirs.state_dir);
 	setup_logfile(&logfile_path);
 
 	let backend = CrosstermBackend::new(io::stdout());
@@ -160,6 +187,8 @@ fn main() {
 	// have been updated.
 	let mut proc_modified: bool;
 	let mut graphs_modified: bool;
+	// The navigation keys pick how to scroll, which is then done to whichever table is focused.
+	let mut scroll: Option<fn(&mut dyn ScrollableTable)>;
 
 	update_widgets(&mut app.widgets, update_seconds);
 	draw(&mut terminal, &mut app);
@@ -182,10 +211,12 @@ fn main() {
 				proc_modified = false;
 				graphs_modified = false;
 				skip_key = false;
+				scroll = None;
 
 				match message.unwrap() {
 					Event::Key(key_event) => {
-						if key_event.modifiers.is_empty() {
+						// Uppercase characters are reported with the shift modifier.
+						if key_event.modifiers.is_empty() || key_event.modifiers == KeyModifiers::SHIFT {
 							match key_event.code {
 								KeyCode::Char('q') => {
 									break
@@ -202,27 +233,22 @@ fn main() {
 									paused = !paused;
 								},
 								KeyCode::Char('j') | KeyCode::Down => {
-									app.widgets.proc.scroll_down();
-									proc_modified = true;
+									scroll = Some(|table| table.scroll_down());
 								},
 								KeyCode::Char('k') | KeyCode::Up => {
-									app.widgets.proc.scroll_up();
-									proc_modified = true;
+									scroll = Some(|table| table.scroll_up());
 								},
 								KeyCode::Char('g') => {
 									if previous_key_event == Some(KeyEvent::from(KeyCode::Char('g'))) {
-										app.widgets.proc.scroll_top();
-										proc_modified = true;
+										scroll = Some(|table| table.scroll_top());
 										skip_key = true;
 									}
 								},
 								KeyCode::Home => {
-									app.widgets.proc.scroll_top();
-									proc_modified = true;
+									scroll = Some(|table| table.scroll_top());
 								},
 								KeyCode::Char('G') | KeyCode::End => {
-									app.widgets.proc.scroll_bottom();
-									proc_modified = true;
+									scroll = Some(|table| table.scroll_bottom());
 								},
 								KeyCode::Char('d') => {
 									if previous_key_event == Some(KeyEvent::from(KeyCode::Char('d'))) {
@@ -233,17 +259,125 @@ fn main() {
 								KeyCode::Char('h') => {
 									app.widgets.cpu.scale_in();
 									app.widgets.mem.scale_in();
+									if let Some(psi) = app.widgets.psi.as_mut() {
+										psi.scale_in();
+									}
 									graphs_modified = true;
 								},
 								KeyCode::Char('l') => {
 									app.widgets.cpu.scale_out();
 									app.widgets.mem.scale_out();
+									if let Some(psi) = app.widgets.psi.as_mut() {
+										psi.scale_out();
+									}
+									graphs_modified = true;
+								},
+								KeyCode::Char('s') => {
+									app.widgets.cpu.toggle_stats();
+									app.widgets.mem.toggle_stats();
+									if let Some(net) = app.widgets.net.as_mut() {
+										net.toggle_stats();
+									}
+									graphs_modified = true;
+								},
+								KeyCode::Char('S') => {
+									app.widgets.cpu.toggle_mean_lines();
+									app.widgets.mem.toggle_mean_lines();
+									if let Some(net) = app.widgets.net.as_mut() {
+										net.toggle_mean_lines();
+									}
+									graphs_modified = true;
+								},
+								KeyCode::Char('M') => {
+									app.widgets.mem.cycle_view();
+									graphs_modified = true;
+								},
+								KeyCode::Char('D') => {
+									if let Some(disk) = app.widgets.disk.as_mut() {
+										disk.cycle_view();
+									}
+									graphs_modified = true;
+								},
+								KeyCode::Char('f') => {
+									if let Some(disk) = app.widgets.disk.as_mut() {
+										disk.toggle_focus();
+									}
+									graphs_modified = true;
+								},
+								KeyCode::Char('u') => {
+									if let Some(disk) = app.widgets.disk.as_mut().filter(|disk| disk.is_focused()) {
+										disk.sort_by_used();
+										graphs_modified = true;
+									}
+								},
+								KeyCode::Char('e') => {
+									if let Some(disk) = app.widgets.disk.as_mut().filter(|disk| disk.is_focused()) {
+										disk.sort_by_free();
+										graphs_modified = true;
+									}
+								},
+								KeyCode::Char('r') => {
+									if let Some(disk) = app.widgets.disk.as_mut().filter(|disk| disk.is_focused()) {
+										disk.sort_by_read();
+										graphs_modified = true;
+									}
+								},
+								KeyCode::Char('w') => {
+									if let Some(disk) = app.widgets.disk.as_mut().filter(|disk| disk.is_focused()) {
+										disk.sort_by_write();
+										graphs_modified = true;
+									}
+								},
+								KeyCode::Char('F') => {
+									if let Some(disk) = app.widgets.disk.as_mut() {
+										disk.toggle_all_filesystems();
+									}
+									graphs_modified = true;
+								},
+								KeyCode::Char('i') => {
+									if let Some(net) = app.widgets.net.as_mut() {
+										net.cycle_interface();
+									}
+									graphs_modified = true;
+								},
+								KeyCode::Char('x') => {
+									if let Some(net) = app.widgets.net.as_mut() {
+										net.cycle_scale();
+									}
+									graphs_modified = true;
+								},
+								KeyCode::Char('I') => {
+									if let Some(net) = app.widgets.net.as_mut() {
+										net.toggle_view();
+									}
+									graphs_modified = true;
+								},
+								KeyCode::Char('N') => {
+									app.widgets.mem.toggle_numa_nodes();
+									graphs_modified = true;
+								},
+								KeyCode::Char('[') => {
+									app.widgets.cpu.select_previous_entry();
+									graphs_modified = true;
+								},
+								KeyCode::Char(']') => {
+									app.widgets.cpu.select_next_entry();
+									graphs_modified = true;
+								},
+								KeyCode::Char('v') => {
+									app.widgets.cpu.toggle_selected_entry();
+									graphs_modified = true;
+								},
+								KeyCode::Char('o') => {
+									app.widgets.cpu.solo_selected_entry();
 									graphs_modified = true;
 								},
 								KeyCode::Esc => {
 									if show_help_menu {
 										show_help_menu = false;
 										draw(&mut terminal, &mut app);
+									} else if app.widgets.cpu.clear_selection() {
+										graphs_modified = true;
 									}
 								}
 								KeyCode::Tab => {
@@ -255,8 +389,13 @@ fn main() {
 									proc_modified = true;
 								},
 								KeyCode::Char('n') => {
-									app.widgets.proc.sort_by_command();
-									proc_modified = true;
+									if let Some(disk) = app.widgets.disk.as_mut().filter(|disk| disk.is_focused()) {
+										disk.sort_by_name();
+										graphs_modified = true;
+									} else {
+										app.widgets.proc.sort_by_command();
+										proc_modified = true;
+									}
 								},
 								KeyCode::Char('c') => {
 									app.widgets.proc.sort_by_cpu();
@@ -274,33 +413,41 @@ fn main() {
 									break
 								},
 								KeyCode::Char('d') => {
-									app.widgets.proc.scroll_half_page_down();
-									proc_modified = true;
+									scroll = Some(|table| table.scroll_half_page_down());
 								},
 								KeyCode::Char('u') => {
-									app.widgets.proc.scroll_half_page_up();
-									proc_modified = true;
+									scroll = Some(|table| table.scroll_half_page_up());
 								},
 								KeyCode::Char('f') => {
-									app.widgets.proc.scroll_full_page_down();
-									proc_modified = true;
+									scroll = Some(|table| table.scroll_full_page_down());
 								},
 								KeyCode::Char('b') => {
-									app.widgets.proc.scroll_full_page_up();
-									proc_modified = true;
+									scroll = Some(|table| table.scroll_full_page_up());
 								},
 								_ => {}
 							}
 						}
 
+						if let Some(scroll) = scroll {
+							let (table, is_graph) = focused_table(&mut app.widgets);
+							scroll(table);
+							if is_graph {
+								graphs_modified = true;
+							} else {
+								proc_modified = true;
+							}
+						}
+
 						previous_key_event = if skip_key {
 							None
 						} else {
 							Some(key_event)
 						};
 					}
-					// TODO: figure out why these aren't working
 					Event::Mouse(mouse_event) => match mouse_event {
+						MouseEvent::Down(MouseButton::Left, x, y, _) if app.widgets.cpu.click(x, y) => {
+							graphs_modified = true;
+						},
 						MouseEvent::ScrollUp(_, _, _) => {
 							app.widgets.proc.scroll_up();
 							proc_modified = true;
@@ -331,5 +478,8 @@ fn main() {
 		}
 	}
 
+	if let Some(net) = app.widgets.net.as_mut() {
+		net.save_usage();
+	}
 	cleanup_terminal();
 }
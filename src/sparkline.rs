@@ -36,7 +36,9 @@ pub struct Sparkline<'a> {
 }
 
 pub enum RenderDirection {
+	/// The first entry of the data is drawn at the left edge, followed by the ones after it.
 	LTR,
+	/// The last entry of the data is drawn at the right edge, preceded by the ones before it.
 	RTL,
 }
 
@@ -108,10 +110,12 @@ impl<'a> Widget for Sparkline<'a> {
 			None => *self.data.iter().max().unwrap_or(&1u64),
 		};
 		let max_index = min(spark_area.width as usize, self.data.len());
-		let mut data = self
-			.data
+		let visible_data = match self.direction {
+			RenderDirection::LTR => &self.data[..max_index],
+			RenderDirection::RTL => &self.data[self.data.len() - max_index..],
+		};
+		let mut data = visible_data
 			.iter()
-			.take(max_index)
 			.map(|e| {
 				if max != 0 {
 					e * u64::from(spark_area.height) * 8 / max
@@ -141,7 +145,7 @@ impl<'a> Widget for Sparkline<'a> {
 				};
 				let x = match self.direction {
 					RenderDirection::LTR => spark_area.left() + i as u16,
-					RenderDirection::RTL => spark_area.right() - i as u16 - 1,
+					RenderDirection::RTL => spark_area.right() - (max_index - i) as u16,
 				};
 				buf.get_mut(x, spark_area.top() + j)
 					.set_symbol(symbol)
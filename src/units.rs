@@ -0,0 +1,191 @@
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Quantity {
+	Bits,
+	Bytes,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Prefix {
+	/// Powers of 1000, e.g. kB and Mbit.
+	Si,
+	/// Powers of 1024, e.g. KiB and Mibit.
+	Iec,
+}
+
+/// How sizes and rates are shown.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Units {
+	pub quantity: Quantity,
+	pub prefix: Prefix,
+}
+
+impl Default for Units {
+	fn default() -> Self {
+		Units {
+			quantity: Quantity::Bytes,
+			prefix: Prefix::Iec,
+		}
+	}
+}
+
+const SI_BYTES: [&str; 7] = ["", "kB", "MB", "GB", "TB", "PB", "EB"];
+const IEC_BYTES: [&str; 7] = ["", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const SI_BITS: [&str; 7] = ["", "kbit", "Mbit", "Gbit", "Tbit", "Pbit", "Ebit"];
+const IEC_BITS: [&str; 7] = ["", "Kibit", "Mibit", "Gibit", "Tibit", "Pibit", "Eibit"];
+
+impl Units {
+	/// Formats a number of bytes with 3 significant digits, e.g. "1.43 MiB" or "12.0 Mbit".
+	pub fn format(self, bytes: u64) -> String {
+		let (value, singular, plural, names) = match (self.quantity, self.prefix) {
+			(Quantity::Bytes, Prefix::Si) => (bytes as f64, "byte", "bytes", &SI_BYTES),
+			(Quantity::Bytes, Prefix::Iec) => (bytes as f64, "byte", "bytes", &IEC_BYTES),
+			(Quantity::Bits, Prefix::Si) => (bytes as f64 * 8.0, "bit", "bits", &SI_BITS),
+			(Quantity::Bits, Prefix::Iec) => (bytes as f64 * 8.0, "bit", "bits", &IEC_BITS),
+		};
+		let base = match self.prefix {
+			Prefix::Si => 1000.0,
+			Prefix::Iec => 1024.0,
+		};
+
+		let mut power = 0;
+		while power + 1 < names.len() && value >= f64::powi(base, power as i32 + 1) {
+			power += 1;
+		}
+		if power == 0 {
+			return format!("{} {}", value, if value == 1.0 { singular } else { plural });
+		}
+
+		let scaled = value / f64::powi(base, power as i32);
+		let decimals = if scaled < 10.0 {
+			2
+		} else if scaled < 100.0 {
+			1
+		} else {
+			0
+		};
+		format!("{:.*} {}", decimals, scaled, names[power])
+	}
+}
+
+/// Units given on the command line, e.g. `bits,si`. Anything that's left out comes from the
+/// global units.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct UnitsArg {
+	quantity: Option<Quantity>,
+	prefix: Option<Prefix>,
+}
+
+impl UnitsArg {
+	pub fn or(self, fallback: Units) -> Units {
+		Units {
+			quantity: self.quantity.unwrap_or(fallback.quantity),
+			prefix: self.prefix.unwrap_or(fallback.prefix),
+		}
+	}
+}
+
+impl FromStr for UnitsArg {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut units = UnitsArg::default();
+		for part in s.split(',') {
+			match part.trim() {
+				"bits" => units.quantity = Some(Quantity::Bits),
+				"bytes" => units.quantity = Some(Quantity::Bytes),
+				"si" => units.prefix = Some(Prefix::Si),
+				"iec" => units.prefix = Some(Prefix::Iec),
+				_ => {
+					return Err(format!(
+						"unknown unit '{}', expected 'bits', 'bytes', 'si', or 'iec'",
+						part
+					))
+				}
+			}
+		}
+		Ok(units)
+	}
+}
+
+/// A size given on the command line, e.g. `50GiB`, `500GB`, or `1.5T`. Single letter prefixes are
+/// powers of 1024.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ByteSize(pub u64);
+
+impl FromStr for ByteSize {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		let split = s
+			.find(|c: char| !(c.is_ascii_digit() || c == '.'))
+			.unwrap_or(s.len());
+		let (number, unit) = s.split_at(split);
+		let number: f64 = number
+			.parse()
+			.map_err(|_| format!("invalid size '{}', expected e.g. '50GiB'", s))?;
+		let multiplier: f64 = match unit.trim().to_lowercase().as_str() {
+			"" | "b" => 1.0,
+			"kb" => 1e3,
+			"mb" => 1e6,
+			"gb" => 1e9,
+			"tb" => 1e12,
+			"k" | "kib" => 1024.0,
+			"m" | "mib" => f64::powi(1024.0, 2),
+			"g" | "gib" => f64::powi(1024.0, 3),
+			"t" | "tib" => f64::powi(1024.0, 4),
+			_ => return Err(format!("unknown size unit '{}'", unit)),
+		};
+		let bytes = (number * multiplier) as u64;
+		if bytes == 0 {
+			return Err(format!("size '{}' must be more than 0 bytes", s));
+		}
+		Ok(ByteSize(bytes))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn it_formats_like_the_size_crate_by_default() {
+		let units = Units::default();
+		assert_eq!(units.format(0), "0 bytes");
+		assert_eq!(units.format(1), "1 byte");
+		assert_eq!(units.format(1023), "1023 bytes");
+		assert_eq!(units.format(1536), "1.50 KiB");
+		assert_eq!(units.format(1_500_000), "1.43 MiB");
+		assert_eq!(units.format(1000 * 1024), "1000 KiB");
+	}
+
+	#[test]
+	fn it_formats_bits_and_si() {
+		let bits_si: UnitsArg = "bits,si".parse().unwrap();
+		let bits_si = bits_si.or(Units::default());
+		assert_eq!(bits_si.format(0), "0 bits");
+		assert_eq!(bits_si.format(125), "1.00 kbit");
+		assert_eq!(bits_si.format(1_500_000), "12.0 Mbit");
+
+		let si: UnitsArg = "si".parse().unwrap();
+		assert_eq!(si.or(Units::default()).format(1_500_000), "1.50 MB");
+	}
+
+	#[test]
+	fn it_rejects_unknown_units() {
+		assert!("bits,kb".parse::<UnitsArg>().is_err());
+	}
+
+	#[test]
+	fn it_parses_sizes() {
+		assert_eq!("500".parse(), Ok(ByteSize(500)));
+		assert_eq!("50GiB".parse(), Ok(ByteSize(50 * 1024 * 1024 * 1024)));
+		assert_eq!("1.5 GB".parse(), Ok(ByteSize(1_500_000_000)));
+		assert_eq!("2t".parse(), Ok(ByteSize(2 * 1024 * 1024 * 1024 * 1024)));
+		assert!("GiB".parse::<ByteSize>().is_err());
+		assert!("5 parsecs".parse::<ByteSize>().is_err());
+		assert!("0GiB".parse::<ByteSize>().is_err());
+	}
+}
@@ -25,6 +25,22 @@ pub fn update_widgets(widgets: &mut Widgets, seconds: Ratio<u64>) {
 		widgets_to_update.push(battery);
 	}
 
+	if let Some(connections) = widgets.connections.as_mut() {
+		widgets_to_update.push(connections);
+	}
+
+	if let Some(protocols) = widgets.protocols.as_mut() {
+		widgets_to_update.push(protocols);
+	}
+
+	if let Some(psi) = widgets.psi.as_mut() {
+		widgets_to_update.push(psi);
+	}
+
+	if let Some(raid) = widgets.raid.as_mut() {
+		widgets_to_update.push(raid);
+	}
+
 	for widget in widgets_to_update {
 		if seconds % widget.get_update_interval() == Ratio::from_integer(0) {
 			widget.update();
@@ -0,0 +1,140 @@
+use std::cmp::Ordering;
+
+use num_rational::Ratio;
+use tui::layout::Rect;
+
+use crate::history::Stats;
+
+/// Labels for a y axis showing percentages from 0 to `max`.
+pub fn percent_labels(max: f64) -> Vec<String> {
+	vec![
+		"0%".to_string(),
+		format!("{:.0}%", max / 2.0),
+		format!("{:.0}%", max),
+	]
+}
+
+/// Labels for an x axis showing the last `horizontal_scale` updates, given that an update happens
+/// every `update_interval` seconds.
+pub fn time_labels(horizontal_scale: u64, update_interval: Ratio<u64>) -> Vec<String> {
+	let seconds = update_interval * horizontal_scale;
+	let seconds = *seconds.numer() as f64 / *seconds.denom() as f64;
+	vec![
+		format_duration(seconds),
+		format_duration(seconds / 2.0),
+		"now".to_string(),
+	]
+}
+
+/// Formats a duration like `1h30m`.
+pub fn format_duration(seconds: f64) -> String {
+	let seconds = seconds.round() as u64;
+	let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+	if hours > 0 {
+		if minutes > 0 {
+			format!("{}h{}m", hours, minutes)
+		} else {
+			format!("{}h", hours)
+		}
+	} else if minutes > 0 {
+		if seconds > 0 {
+			format!("{}m{}s", minutes, seconds)
+		} else {
+			format!("{}m", minutes)
+		}
+	} else {
+		format!("{}s", seconds)
+	}
+}
+
+/// Summary of a graph of percentages for use in a legend.
+pub fn percent_stats_label(stats: &Stats) -> String {
+	format!(
+		"min {:3.0}% max {:3.0}% avg {:3.0}% p95 {:3.0}%",
+		stats.min, stats.max, stats.mean, stats.p95
+	)
+}
+
+/// Points that draw a dashed horizontal line at `y` when plotted as a scatter graph in
+/// `graph_area`, with a dot every other column.
+pub fn dashed_line(y: f64, x_bounds: [f64; 2], graph_area: Rect) -> Vec<(f64, f64)> {
+	let columns = u64::max(graph_area.width as u64, 1);
+	let step = (x_bounds[1] - x_bounds[0]) / columns as f64 * 2.0;
+	(0..columns / 2)
+		.map(|i| (x_bounds[0] + step * i as f64, y))
+		.collect()
+}
+
+/// Points that fill the area under each series when the series are stacked on top of each other
+/// and plotted as Braille scatter graphs in `graph_area`. Series are ordered from bottom to top and
+/// the returned points are grouped the same way.
+pub fn stacked_area(
+	series: &[&[(f64, f64)]],
+	x_bounds: [f64; 2],
+	y_max: f64,
+	graph_area: Rect,
+) -> Vec<Vec<(f64, f64)>> {
+	let mut layers = vec![Vec::new(); series.len()];
+
+	// Braille characters have 2x4 dots.
+	let columns = graph_area.width as usize * 2;
+	let rows = graph_area.height as usize * 4;
+	for column in 0..columns {
+		let x = x_bounds[0] + (column as f64 + 0.5) * (x_bounds[1] - x_bounds[0]) / columns as f64;
+		let values: Option<Vec<f64>> = series.iter().map(|data| value_at(data, x)).collect();
+		let values = match values {
+			Some(values) => values,
+			None => continue,
+		};
+		for row in 0..rows {
+			let y = (row as f64 + 0.5) * y_max / rows as f64;
+			let mut top = 0.0;
+			for (layer, value) in layers.iter_mut().zip(values.iter()) {
+				top += value;
+				if y < top {
+					layer.push((x, y));
+					break;
+				}
+			}
+		}
+	}
+
+	layers
+}
+
+// Returns the value of the most recent point at or before `x`, or `None` if `x` is outside of the
+// data.
+fn value_at(data: &[(f64, f64)], x: f64) -> Option<f64> {
+	if data.last()?.0 < x {
+		return None;
+	}
+	let index = match data
+		.binary_search_by(|(point_x, _y)| point_x.partial_cmp(&x).unwrap_or(Ordering::Less))
+	{
+		Ok(index) => index,
+		Err(0) => return None,
+		Err(index) => index - 1,
+	};
+	Some(data[index].1)
+}
+
+/// Returns the area that a bordered `Chart` with the given axis labels leaves for plotting data,
+/// which is where legends are drawn.
+pub fn graph_area(area: Rect, x_labels: &[String], y_labels: &[String]) -> Rect {
+	let labels_width = y_labels
+		.iter()
+		.chain(x_labels.first())
+		.map(|label| label.len() as u16)
+		.max()
+		.unwrap_or_default();
+	// 1 for the border plus 1 for the y axis line
+	let x = u16::min(area.x + labels_width + 2, area.right());
+	// 1 for the border plus 2 for the x axis labels and line
+	let height = area.height.saturating_sub(4);
+	Rect {
+		x,
+		y: area.y + 1,
+		width: area.right().saturating_sub(x + 1),
+		height,
+	}
+}
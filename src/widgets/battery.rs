@@ -8,8 +8,9 @@ use tui::symbols::Marker;
 use tui::widgets::{Axis, Chart, Dataset, GraphType, Widget};
 
 use crate::colorscheme::Colorscheme;
+use crate::history::History;
 use crate::update::UpdatableWidget;
-use crate::widgets::block;
+use crate::widgets::{axis, block};
 
 pub struct BatteryWidget<'a> {
 	title: String,
@@ -19,7 +20,7 @@ pub struct BatteryWidget<'a> {
 	horizontal_scale: u64,
 
 	update_count: u64,
-	battery_data: HashMap<String, Vec<(f64, f64)>>,
+	battery_data: HashMap<String, History<(f64, f64)>>,
 	manager: Manager,
 }
 
@@ -81,24 +82,44 @@ impl Widget for &BatteryWidget<'_> {
 					.marker(Marker::Braille)
 					.style(self.colorscheme.battery_lines[i % self.colorscheme.battery_lines.len()])
 					.graph_type(GraphType::Line)
-					.data(&data)
+					.data(data.as_slice())
 			})
 			.collect();
 
+		let x_labels = axis::time_labels(self.horizontal_scale, self.update_interval);
+		let y_labels = axis::percent_labels(100.0);
+		let graph_area = axis::graph_area(area, &x_labels, &y_labels);
+
 		Chart::<String, String>::default()
 			.block(block::new(self.colorscheme, &self.title))
-			.x_axis(Axis::default().bounds([
-				self.update_count as f64 - self.horizontal_scale as f64,
-				self.update_count as f64 + 1.0,
-			]))
-			.y_axis(Axis::default().bounds([0.0, 100.0]))
+			.x_axis(
+				Axis::default()
+					.bounds([
+						self.update_count as f64 - self.horizontal_scale as f64,
+						self.update_count as f64 + 1.0,
+					])
+					.labels(&x_labels)
+					.style(self.colorscheme.borders)
+					.labels_style(self.colorscheme.text),
+			)
+			.y_axis(
+				Axis::default()
+					.bounds([0.0, 100.0])
+					.labels(&y_labels)
+					.style(self.colorscheme.borders)
+					.labels_style(self.colorscheme.text),
+			)
 			.datasets(&datasets)
 			.render(area, buf);
 
 		for (i, data) in self.battery_data.iter().enumerate() {
+			let y = graph_area.y + 1 + i as u16;
+			if y >= graph_area.bottom() {
+				break;
+			}
 			buf.set_string(
-				area.x + 3,
-				area.y + 2 + i as u16,
+				graph_area.x + 1,
+				y,
 				format!("{} {:3.0}%", data.0, data.1.last().unwrap().1),
 				self.colorscheme.battery_lines[i % self.colorscheme.battery_lines.len()],
 			);
@@ -0,0 +1,202 @@
+mod procnet;
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use num_rational::Ratio;
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Rect};
+use tui::style::Modifier;
+use tui::widgets::{Row, Table, Widget};
+
+use crate::colorscheme::Colorscheme;
+use crate::update::UpdatableWidget;
+use crate::widgets::block;
+
+use self::procnet::{Connection, SocketOwner};
+
+const PROC_ROOT: &str = "/proc";
+
+// Fits an IPv4 address and port, or a short IPv6 one.
+const MAX_ADDRESS_WIDTH: u16 = 24;
+
+pub struct ConnectionsWidget<'a> {
+	title: String,
+	update_interval: Ratio<u64>,
+	colorscheme: &'a Colorscheme,
+
+	proc_root: PathBuf,
+
+	connections: Vec<(Connection, Option<SocketOwner>)>,
+}
+
+impl ConnectionsWidget<'_> {
+	pub fn new(colorscheme: &Colorscheme) -> ConnectionsWidget {
+		ConnectionsWidget {
+			title: " Connections ".to_string(),
+			update_interval: Ratio::from_integer(1),
+			colorscheme,
+
+			proc_root: PathBuf::from(PROC_ROOT),
+
+			connections: Vec::new(),
+		}
+	}
+
+	// e.g. "TCP: 12 ESTABLISHED, 3 TIME_WAIT, 5 LISTEN  UDP: 4"
+	fn summary(&self) -> String {
+		let mut tcp_states: BTreeMap<u8, usize> = BTreeMap::new();
+		let mut udp_count = 0;
+		for (connection, _owner) in self.connections.iter() {
+			if connection.protocol.is_tcp() {
+				*tcp_states.entry(connection.state).or_default() += 1;
+			} else {
+				udp_count += 1;
+			}
+		}
+		let tcp_summary = tcp_states
+			.iter()
+			.map(|(state, count)| format!("{} {}", count, procnet::tcp_state_name(*state)))
+			.collect::<Vec<_>>()
+			.join(", ");
+		format!("TCP: {}  UDP: {}", tcp_summary, udp_count)
+	}
+}
+
+impl UpdatableWidget for ConnectionsWidget<'_> {
+	fn update(&mut self) {
+		let mut owners = procnet::read_socket_owners(&self.proc_root);
+		let mut connections: Vec<(Connection, Option<SocketOwner>)> =
+			procnet::read_connections(&self.proc_root)
+				.into_iter()
+				.map(|connection| {
+					let owner = owners.remove(&connection.inode);
+					(connection, owner)
+				})
+				.collect();
+		// TCP before UDP, with established connections first and listening sockets last.
+		connections.sort_by_key(|(connection, _owner)| {
+			(
+				!connection.protocol.is_tcp(),
+				procnet::tcp_state_rank(connection.state),
+				connection.local.port(),
+			)
+		});
+		self.connections = connections;
+	}
+
+	fn get_update_interval(&self) -> Ratio<u64> {
+		self.update_interval
+	}
+}
+
+// Shows unbound addresses like netstat does.
+fn format_addr(addr: &SocketAddr) -> String {
+	if addr.ip().is_unspecified() && addr.port() == 0 {
+		"*".to_string()
+	} else {
+		addr.to_string()
+	}
+}
+
+impl Widget for &ConnectionsWidget<'_> {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		let title = format!("{}({}) ", self.title, self.connections.len());
+		block::new(self.colorscheme, &title).render(area, buf);
+
+		if area.height < 3 {
+			return;
+		}
+
+		buf.set_stringn(
+			area.x + 1,
+			area.y + 1,
+			self.summary(),
+			area.width.saturating_sub(2) as usize,
+			self.colorscheme.text.modifier(Modifier::BOLD),
+		);
+
+		let table_area = Rect {
+			x: area.x + 1,
+			y: area.y + 2,
+			width: area.width.saturating_sub(2),
+			height: area.height.saturating_sub(3),
+		};
+
+		// Proto, State, Recv-Q, Send-Q, and the spaces between all 7 columns take a fixed width. The
+		// addresses come next since they're what identifies a connection, and the process gets
+		// what's left.
+		let fixed_width = 5 + 11 + 6 + 6 + 6;
+		let address_width = u16::min(
+			table_area.width.saturating_sub(fixed_width) / 2,
+			MAX_ADDRESS_WIDTH,
+		);
+		let process_width = table_area
+			.width
+			.saturating_sub(fixed_width + address_width * 2);
+		let mut widths = vec![
+			Constraint::Length(5),
+			Constraint::Length(address_width),
+			Constraint::Length(address_width),
+			Constraint::Length(11),
+			Constraint::Length(6),
+			Constraint::Length(6),
+		];
+		if process_width > 0 {
+			widths.push(Constraint::Length(process_width));
+		}
+
+		Table::new(
+			[
+				"Proto", "Local", "Remote", "State", "Recv-Q", "Send-Q", "Process",
+			]
+			.iter(),
+			self.connections.iter().map(|(connection, owner)| {
+				Row::StyledData(
+					vec![
+						connection.protocol.name().to_string(),
+						format_addr(&connection.local),
+						format_addr(&connection.remote),
+						if connection.protocol.is_tcp() {
+							procnet::tcp_state_name(connection.state).to_string()
+						} else {
+							String::new()
+						},
+						connection.recv_queue.to_string(),
+						connection.send_queue.to_string(),
+						owner
+							.as_ref()
+							.map(|owner| format!("{}/{}", owner.pid, owner.name))
+							.unwrap_or_default(),
+					]
+					.into_iter(),
+					self.colorscheme.text,
+				)
+			}),
+		)
+		.header_style(self.colorscheme.text.modifier(Modifier::BOLD))
+		.widths(&widths)
+		.column_spacing(1)
+		.header_gap(0)
+		.render(table_area, buf);
+
+		// The table doesn't scroll, so say how many connections didn't fit in the bottom border.
+		let hidden = self
+			.connections
+			.len()
+			.saturating_sub(table_area.height.saturating_sub(1) as usize);
+		if hidden > 0 {
+			let text = format!(" ({} more) ", hidden);
+			let text_width = text.chars().count() as u16;
+			if area.width >= text_width + 2 {
+				buf.set_string(
+					area.right() - 1 - text_width,
+					area.bottom() - 1,
+					text,
+					self.colorscheme.titles,
+				);
+			}
+		}
+	}
+}
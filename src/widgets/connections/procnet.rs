@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Protocol {
+	Tcp,
+	Tcp6,
+	Udp,
+	Udp6,
+}
+
+impl Protocol {
+	const ALL: [Protocol; 4] = [Protocol::Tcp, Protocol::Tcp6, Protocol::Udp, Protocol::Udp6];
+
+	/// The name of the protocol's file in /proc/net.
+	pub fn name(self) -> &'static str {
+		match self {
+			Protocol::Tcp => "tcp",
+			Protocol::Tcp6 => "tcp6",
+			Protocol::Udp => "udp",
+			Protocol::Udp6 => "udp6",
+		}
+	}
+
+	pub fn is_tcp(self) -> bool {
+		self == Protocol::Tcp || self == Protocol::Tcp6
+	}
+}
+
+/// The names of the kernel's TCP states, indexed by their number in /proc/net/tcp.
+const TCP_STATES: [&str; 13] = [
+	"",
+	"ESTABLISHED",
+	"SYN_SENT",
+	"SYN_RECV",
+	"FIN_WAIT1",
+	"FIN_WAIT2",
+	"TIME_WAIT",
+	"CLOSE",
+	"CLOSE_WAIT",
+	"LAST_ACK",
+	"LISTEN",
+	"CLOSING",
+	"NEW_SYN_RECV",
+];
+
+const TCP_LISTEN: u8 = 10;
+
+pub fn tcp_state_name(state: u8) -> &'static str {
+	TCP_STATES.get(state as usize).cloned().unwrap_or("UNKNOWN")
+}
+
+/// Where a TCP state is sorted, which is in the kernel's order except that listening sockets go
+/// after everything else.
+pub fn tcp_state_rank(state: u8) -> u8 {
+	if state == TCP_LISTEN {
+		TCP_STATES.len() as u8
+	} else {
+		state
+	}
+}
+
+/// A socket from one of the tables in /proc/net.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Connection {
+	pub protocol: Protocol,
+	pub local: SocketAddr,
+	pub remote: SocketAddr,
+	/// One of the TCP states, which UDP sockets reuse for whether they're connected.
+	pub state: u8,
+	pub send_queue: u64,
+	pub recv_queue: u64,
+	pub inode: u64,
+}
+
+/// A process that has a socket open.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SocketOwner {
+	pub pid: u32,
+	pub name: String,
+}
+
+/// Parses one of /proc/net/{tcp,tcp6,udp,udp6}, e.g.:
+///   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+///    0: 0100007F:0035 00000000:0000 0A 00000000:00000000 00:00000000 00000000   101        0 16543 1 ...
+pub fn parse_proc_net(contents: &str, protocol: Protocol) -> Vec<Connection> {
+	contents
+		.lines()
+		.skip(1)
+		.filter_map(|line| parse_connection(line, protocol))
+		.collect()
+}
+
+fn parse_connection(line: &str, protocol: Protocol) -> Option<Connection> {
+	let fields: Vec<&str> = line.split_whitespace().collect();
+	let mut queues = fields.get(4)?.split(':');
+	Some(Connection {
+		protocol,
+		local: parse_socket_addr(fields.get(1)?)?,
+		remote: parse_socket_addr(fields.get(2)?)?,
+		state: u8::from_str_radix(fields.get(3)?, 16).ok()?,
+		send_queue: u64::from_str_radix(queues.next()?, 16).ok()?,
+		recv_queue: u64::from_str_radix(queues.next()?, 16).ok()?,
+		inode: fields.get(9)?.parse().ok()?,
+	})
+}
+
+// The kernel prints addresses as 32 bit words in host byte order, e.g. "0100007F:0035" is
+// 127.0.0.1:53 on little endian machines.
+fn parse_socket_addr(field: &str) -> Option<SocketAddr> {
+	let mut parts = field.split(':');
+	let address = parts.next()?;
+	let port = u16::from_str_radix(parts.next()?, 16).ok()?;
+
+	let mut bytes = Vec::with_capacity(16);
+	for index in (0..address.len()).step_by(8) {
+		let word = u32::from_str_radix(address.get(index..index + 8)?, 16).ok()?;
+		bytes.extend_from_slice(&word.to_ne_bytes());
+	}
+	let ip = match bytes.len() {
+		4 => IpAddr::V4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+		16 => {
+			let mut octets = [0; 16];
+			octets.copy_from_slice(&bytes);
+			IpAddr::V6(Ipv6Addr::from(octets))
+		}
+		_ => return None,
+	};
+	Some(SocketAddr::new(ip, port))
+}
+
+/// Reads the sockets of every protocol from `<proc_root>/net`, skipping protocols that the kernel
+/// doesn't support.
+pub fn read_connections(proc_root: &Path) -> Vec<Connection> {
+	Protocol::ALL
+		.iter()
+		.flat_map(|protocol| {
+			fs::read_to_string(proc_root.join("net").join(protocol.name()))
+				.map(|contents| parse_proc_net(&contents, *protocol))
+				.unwrap_or_default()
+		})
+		.collect()
+}
+
+/// Maps socket inodes to the processes that have them open by following the links in
+/// `<proc_root>/<pid>/fd`. Processes whose file descriptors we aren't allowed to read are skipped.
+pub fn read_socket_owners(proc_root: &Path) -> HashMap<u64, SocketOwner> {
+	let mut owners = HashMap::new();
+	let entries = match fs::read_dir(proc_root) {
+		Ok(entries) => entries,
+		Err(_) => return owners,
+	};
+	for entry in entries.filter_map(|entry| entry.ok()) {
+		let pid: u32 = match entry.file_name().to_string_lossy().parse() {
+			Ok(pid) => pid,
+			Err(_) => continue,
+		};
+		let fds = match fs::read_dir(entry.path().join("fd")) {
+			Ok(fds) => fds,
+			Err(_) => continue,
+		};
+		let mut name = None;
+		for fd in fds.filter_map(|fd| fd.ok()) {
+			// e.g. "socket:[16543]"
+			let inode = match fs::read_link(fd.path()) {
+				Ok(target) => parse_socket_link(&target.to_string_lossy()),
+				Err(_) => continue,
+			};
+			if let Some(inode) = inode {
+				let name = name.get_or_insert_with(|| {
+					fs::read_to_string(entry.path().join("comm"))
+						.map(|comm| comm.trim_end().to_string())
+						.unwrap_or_default()
+				});
+				owners.insert(
+					inode,
+					SocketOwner {
+						pid,
+						name: name.clone(),
+					},
+				);
+			}
+		}
+	}
+	owners
+}
+
+fn parse_socket_link(target: &str) -> Option<u64> {
+	if !target.starts_with("socket:[") || !target.ends_with(']') {
+		return None;
+	}
+	target["socket:[".len()..target.len() - 1].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::PathBuf;
+
+	use super::*;
+
+	fn fixture_root() -> PathBuf {
+		Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/proc")
+	}
+
+	#[test]
+	fn it_parses_connections() {
+		let connections = read_connections(&fixture_root());
+		assert_eq!(connections.len(), 6);
+
+		assert_eq!(
+			connections[0],
+			Connection {
+				protocol: Protocol::Tcp,
+				local: "127.0.0.53:53".parse().unwrap(),
+				remote: "0.0.0.0:0".parse().unwrap(),
+				state: 0x0A,
+				send_queue: 0,
+				recv_queue: 0,
+				inode: 16543,
+			}
+		);
+		assert_eq!(tcp_state_name(connections[0].state), "LISTEN");
+		assert_eq!(tcp_state_name(connections[1].state), "ESTABLISHED");
+		// Listening sockets sort after the other states, including the ones numbered after them.
+		assert!(tcp_state_rank(connections[0].state) > tcp_state_rank(12));
+		assert!(tcp_state_rank(connections[1].state) < tcp_state_rank(11));
+		assert_eq!(connections[1].remote, "93.184.216.34:443".parse().unwrap());
+		assert_eq!(connections[1].send_queue, 0x2a);
+		assert_eq!(connections[1].recv_queue, 0x10);
+
+		let tcp6: Vec<&Connection> = connections
+			.iter()
+			.filter(|connection| connection.protocol == Protocol::Tcp6)
+			.collect();
+		assert_eq!(tcp6[0].local, "[::1]:631".parse().unwrap());
+		assert_eq!(tcp6[1].local, "[2001:db8::1]:22".parse().unwrap());
+
+		assert!(connections
+			.iter()
+			.any(|connection| connection.protocol == Protocol::Udp));
+		assert!(connections
+			.iter()
+			.any(|connection| connection.protocol == Protocol::Udp6));
+	}
+
+	#[test]
+	fn it_finds_socket_owners() {
+		let owners = read_socket_owners(&fixture_root());
+		assert_eq!(
+			owners.get(&16543),
+			Some(&SocketOwner {
+				pid: 412,
+				name: "systemd-resolve".to_string(),
+			})
+		);
+		assert_eq!(owners.get(&24680), None);
+	}
+
+	#[test]
+	fn it_skips_missing_protocols() {
+		assert!(read_connections(Path::new("/nonexistent")).is_empty());
+	}
+}
@@ -2,12 +2,14 @@ use num_rational::Ratio;
 use psutil::cpu;
 use tui::buffer::Buffer;
 use tui::layout::Rect;
+use tui::style::{Modifier, Style};
 use tui::symbols::Marker;
 use tui::widgets::{Axis, Chart, Dataset, GraphType, Widget};
 
 use crate::colorscheme::Colorscheme;
+use crate::history::{History, Stats, MAX_HORIZONTAL_SCALE};
 use crate::update::UpdatableWidget;
-use crate::widgets::block;
+use crate::widgets::{axis, block};
 
 const HORIZONTAL_SCALE_DELTA: u64 = 25;
 
@@ -25,8 +27,17 @@ pub struct CpuWidget<'a> {
 	show_average: bool,
 	show_percpu: bool,
 
-	average_data: Vec<(f64, f64)>,
-	percpu_data: Vec<Vec<(f64, f64)>>,
+	show_stats: bool,
+	show_mean_lines: bool,
+
+	average_data: History<(f64, f64)>,
+	percpu_data: Vec<History<(f64, f64)>>,
+
+	// Indexed by legend entry, which is the AVRG line (if shown) followed by each CPU.
+	hidden_entries: Vec<bool>,
+	selected_entry: Option<usize>,
+	// Where the legend was last drawn so that mouse clicks can be mapped to entries.
+	legend_area: Rect,
 
 	collector: cpu::CpuPercentCollector,
 }
@@ -54,9 +65,16 @@ impl CpuWidget<'_> {
 			show_average,
 			show_percpu,
 
-			average_data: vec![(update_count as f64, 0.0)],
+			show_stats: false,
+			show_mean_lines: false,
+
+			average_data: History::default(),
 			percpu_data: Vec::new(),
 
+			hidden_entries: Vec::new(),
+			selected_entry: None,
+			legend_area: Rect::default(),
+
 			collector: cpu::CpuPercentCollector::new().unwrap(),
 		};
 
@@ -68,17 +86,103 @@ impl CpuWidget<'_> {
 			}
 		}
 
+		cpu_widget.average_data.push((update_count as f64, 0.0));
 		if cpu_widget.show_percpu {
 			for _i in 0..cpu_widget.cpu_count {
-				cpu_widget
-					.percpu_data
-					.push(vec![(update_count as f64, 0.0)]);
+				let mut data = History::default();
+				data.push((update_count as f64, 0.0));
+				cpu_widget.percpu_data.push(data);
 			}
 		}
 
+		cpu_widget.hidden_entries = vec![false; cpu_widget.legend_len()];
+
 		cpu_widget
 	}
 
+	fn legend_len(&self) -> usize {
+		let average_len = if self.show_average { 1 } else { 0 };
+		let percpu_len = if self.show_percpu { self.cpu_count } else { 0 };
+		average_len + percpu_len
+	}
+
+	fn legend_entries(&self) -> Vec<(String, &[(f64, f64)])> {
+		let mut entries = Vec::new();
+		if self.show_average {
+			entries.push(("AVRG".to_string(), self.average_data.as_slice()));
+		}
+		if self.show_percpu {
+			for (i, data) in self.percpu_data.iter().enumerate() {
+				entries.push((format!("CPU{}", i), data.as_slice()));
+			}
+		}
+		entries
+	}
+
+	fn line_style(&self, entry: usize) -> Style {
+		self.colorscheme.cpu_lines[entry % self.colorscheme.cpu_lines.len()]
+	}
+
+	pub fn select_next_entry(&mut self) {
+		self.selected_entry = match self.selected_entry {
+			Some(entry) => Some(usize::min(entry + 1, self.legend_len() - 1)),
+			None => Some(0),
+		};
+	}
+
+	pub fn select_previous_entry(&mut self) {
+		self.selected_entry = match self.selected_entry {
+			Some(entry) => Some(entry.saturating_sub(1)),
+			None => Some(self.legend_len() - 1),
+		};
+	}
+
+	/// Returns true if there was a selection to clear.
+	pub fn clear_selection(&mut self) -> bool {
+		self.selected_entry.take().is_some()
+	}
+
+	pub fn toggle_selected_entry(&mut self) {
+		if let Some(entry) = self.selected_entry {
+			self.hidden_entries[entry] = !self.hidden_entries[entry];
+		}
+	}
+
+	/// Hides every entry except for the selected one, or shows every entry again if the selected
+	/// entry is already the only one shown.
+	pub fn solo_selected_entry(&mut self) {
+		if let Some(entry) = self.selected_entry {
+			let is_solo = self
+				.hidden_entries
+				.iter()
+				.enumerate()
+				.all(|(i, hidden)| *hidden == (i != entry));
+			for (i, hidden) in self.hidden_entries.iter_mut().enumerate() {
+				*hidden = !is_solo && i != entry;
+			}
+		}
+	}
+
+	/// Selects and toggles the legend entry at the given screen position. Returns false if the
+	/// position is outside of the legend.
+	pub fn click(&mut self, x: u16, y: u16) -> bool {
+		let area = self.legend_area;
+		if x < area.left() || x >= area.right() || y < area.top() || y >= area.bottom() {
+			return false;
+		}
+		self.selected_entry = Some((y - area.top()) as usize);
+		self.toggle_selected_entry();
+		true
+	}
+
+	pub fn toggle_stats(&mut self) {
+		self.show_stats = !self.show_stats;
+	}
+
+	pub fn toggle_mean_lines(&mut self) {
+		self.show_mean_lines = !self.show_mean_lines;
+	}
+
 	pub fn scale_in(&mut self) {
 		if self.horizontal_scale > HORIZONTAL_SCALE_DELTA {
 			self.horizontal_scale -= HORIZONTAL_SCALE_DELTA;
@@ -86,7 +190,9 @@ impl CpuWidget<'_> {
 	}
 
 	pub fn scale_out(&mut self) {
-		self.horizontal_scale += HORIZONTAL_SCALE_DELTA;
+		if self.horizontal_scale + HORIZONTAL_SCALE_DELTA <= MAX_HORIZONTAL_SCALE {
+			self.horizontal_scale += HORIZONTAL_SCALE_DELTA;
+		}
 	}
 }
 
@@ -116,68 +222,107 @@ impl UpdatableWidget for CpuWidget<'_> {
 	}
 }
 
-impl Widget for &CpuWidget<'_> {
+impl Widget for &mut CpuWidget<'_> {
 	fn render(self, area: Rect, buf: &mut Buffer) {
+		let entries = self.legend_entries();
+
+		let x_bounds = [
+			self.update_count as f64 - self.horizontal_scale as f64,
+			self.update_count as f64 + 1.0,
+		];
+		let x_labels = axis::time_labels(self.horizontal_scale, self.update_interval);
+		let y_labels = axis::percent_labels(100.0);
+		let graph_area = axis::graph_area(area, &x_labels, &y_labels);
+
+		let stats: Vec<Option<Stats>> = entries
+			.iter()
+			.map(|(_label, data)| Stats::of_window(data, x_bounds[0]))
+			.collect();
+		let mean_lines: Vec<Vec<(f64, f64)>> = stats
+			.iter()
+			.map(|stats| match stats {
+				Some(stats) if self.show_mean_lines => {
+					axis::dashed_line(stats.mean, x_bounds, graph_area)
+				}
+				_ => Vec::new(),
+			})
+			.collect();
+
 		let mut datasets = Vec::new();
-		if self.show_average {
+		for (i, (_label, data)) in entries.iter().enumerate() {
+			if self.hidden_entries[i] {
+				continue;
+			}
 			datasets.push(
 				Dataset::default()
 					.marker(Marker::Braille)
 					.graph_type(GraphType::Line)
-					.style(self.colorscheme.cpu_lines[0])
-					.data(&self.average_data),
-			)
-		}
-		if self.show_percpu {
-			let offset = if self.show_average { 1 } else { 0 };
-			for i in 0..self.cpu_count {
+					.style(self.line_style(i))
+					.data(data),
+			);
+			if !mean_lines[i].is_empty() {
 				datasets.push(
 					Dataset::default()
 						.marker(Marker::Braille)
-						.graph_type(GraphType::Line)
-						.style(
-							self.colorscheme.cpu_lines
-								[(i + offset as usize) % self.colorscheme.cpu_lines.len()],
-						)
-						.data(&self.percpu_data[i]),
-				)
+						.graph_type(GraphType::Scatter)
+						.style(self.line_style(i))
+						.data(&mean_lines[i]),
+				);
 			}
 		}
 
 		Chart::<String, String>::default()
 			.block(block::new(self.colorscheme, &self.title))
-			.x_axis(Axis::default().bounds([
-				self.update_count as f64 - self.horizontal_scale as f64,
-				self.update_count as f64 + 1.0,
-			]))
-			.y_axis(Axis::default().bounds([0.0, 100.0]))
+			.x_axis(
+				Axis::default()
+					.bounds(x_bounds)
+					.labels(&x_labels)
+					.style(self.colorscheme.borders)
+					.labels_style(self.colorscheme.text),
+			)
+			.y_axis(
+				Axis::default()
+					.bounds([0.0, 100.0])
+					.labels(&y_labels)
+					.style(self.colorscheme.borders)
+					.labels_style(self.colorscheme.text),
+			)
 			.datasets(&datasets)
 			.render(area, buf);
 
-		if self.show_average {
-			buf.set_string(
-				area.x + 3,
-				area.y + 2,
-				format!("AVRG {:3.0}%", self.average_data.last().unwrap().1),
-				self.colorscheme.cpu_lines[0],
-			);
-		}
-
-		if self.show_percpu {
-			let offset = if self.show_average { 1 } else { 0 };
-			for i in 0..self.cpu_count {
-				let y = area.y + 2 + offset + i as u16;
-				if y >= area.bottom() - 1 {
-					break;
-				}
-				buf.set_string(
-					area.x + 3,
-					y,
-					format!("CPU{} {:3.0}%", i, self.percpu_data[i].last().unwrap().1),
-					self.colorscheme.cpu_lines
-						[(i + offset as usize) % self.colorscheme.cpu_lines.len()],
-				);
+		let mut legend_area = Rect {
+			x: graph_area.x + 1,
+			y: graph_area.y + 1,
+			width: 0,
+			height: 0,
+		};
+		for (i, (label, data)) in entries.iter().enumerate() {
+			let y = legend_area.y + i as u16;
+			if y >= graph_area.bottom() {
+				break;
 			}
+			let mut text = format!("{} {:3.0}%", label, data.last().unwrap().1);
+			if let (true, Some(stats)) = (self.show_stats, &stats[i]) {
+				text = format!("{}  {}", text, axis::percent_stats_label(stats));
+			}
+			let mut style = if self.hidden_entries[i] {
+				self.colorscheme.text
+			} else {
+				self.line_style(i)
+			};
+			if self.selected_entry == Some(i) {
+				style = style.modifier(Modifier::REVERSED);
+			}
+			legend_area.width = u16::max(legend_area.width, text.len() as u16);
+			legend_area.height += 1;
+			buf.set_stringn(
+				legend_area.x,
+				y,
+				text,
+				graph_area.right().saturating_sub(legend_area.x) as usize,
+				style,
+			);
 		}
+		self.legend_area = legend_area;
 	}
 }
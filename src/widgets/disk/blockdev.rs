@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::Path;
+
+const CLASS_DIR: &str = "/sys/class/block";
+
+// Device mapper targets can be stacked, but never this deep, so this only protects us from loops.
+const MAX_DEPTH: usize = 8;
+
+/// A block device and the devices it's built on, e.g. an LVM volume on a dm-crypt device on a
+/// partition of a disk.
+#[derive(Clone)]
+pub struct BlockDevice {
+	/// The kernel's name for the device, which is what /proc/diskstats uses.
+	pub name: String,
+	/// The name that device mapper gave the device, e.g. `vg-root`.
+	pub label: Option<String>,
+	pub lower: Vec<BlockDevice>,
+}
+
+impl BlockDevice {
+	/// Walks /sys/class/block to find the devices under `name`.
+	pub fn stack(name: &str) -> BlockDevice {
+		BlockDevice::stack_at_depth(Path::new(CLASS_DIR), name, 0)
+	}
+
+	fn stack_at_depth(class_dir: &Path, name: &str, depth: usize) -> BlockDevice {
+		let path = class_dir.join(name);
+		let lower = if depth >= MAX_DEPTH {
+			Vec::new()
+		} else {
+			lower_device_names(&path)
+				.iter()
+				.map(|lower_name| BlockDevice::stack_at_depth(class_dir, lower_name, depth + 1))
+				.collect()
+		};
+		BlockDevice {
+			name: name.to_string(),
+			label: read_label(&path),
+			lower,
+		}
+	}
+
+	/// The device and all of the devices under it, paired with their depth in the stack.
+	pub fn flatten(&self) -> Vec<(usize, &BlockDevice)> {
+		let mut devices = Vec::new();
+		self.flatten_into(0, &mut devices);
+		devices
+	}
+
+	fn flatten_into<'a>(&'a self, depth: usize, devices: &mut Vec<(usize, &'a BlockDevice)>) {
+		devices.push((depth, self));
+		for lower in &self.lower {
+			lower.flatten_into(depth + 1, devices);
+		}
+	}
+}
+
+// dm and md devices list the devices they're built on in `slaves`, and partitions are
+// subdirectories of their disk.
+fn lower_device_names(path: &Path) -> Vec<String> {
+	if let Ok(entries) = fs::read_dir(path.join("slaves")) {
+		let mut names: Vec<String> = entries
+			.filter_map(|entry| entry.ok())
+			.map(|entry| entry.file_name().to_string_lossy().into_owned())
+			.collect();
+		if !names.is_empty() {
+			names.sort();
+			return names;
+		}
+	}
+	if path.join("partition").exists() {
+		if let Some(disk) = fs::canonicalize(path).ok().and_then(|path| {
+			path.parent().and_then(|parent| {
+				parent
+					.file_name()
+					.map(|name| name.to_string_lossy().into_owned())
+			})
+		}) {
+			return vec![disk];
+		}
+	}
+	Vec::new()
+}
+
+fn read_label(path: &Path) -> Option<String> {
+	let label = fs::read_to_string(path.join("dm").join("name")).ok()?;
+	let label = label.trim();
+	if label.is_empty() {
+		None
+	} else {
+		Some(label.to_string())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn it_walks_the_device_stack() {
+		// An LVM volume on a dm-crypt device on the second partition of sda.
+		let class_dir =
+			Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sys/class/block");
+		let stack = BlockDevice::stack_at_depth(&class_dir, "dm-1", 0);
+		let devices: Vec<(usize, &str, Option<String>)> = stack
+			.flatten()
+			.into_iter()
+			.map(|(depth, device)| (depth, device.name.as_str(), device.label.clone()))
+			.collect();
+		assert_eq!(
+			devices,
+			vec![
+				(0, "dm-1", Some("vg-root".to_string())),
+				(1, "dm-0", Some("cryptroot".to_string())),
+				(2, "sda2", None),
+				(3, "sda", None),
+			]
+		);
+	}
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+
+// /proc/diskstats always counts in 512 byte sectors, regardless of the device's sector size.
+const SECTOR_SIZE: u64 = 512;
+
+/// Cumulative I/O counters of a block device.
+#[derive(Default, Clone, Copy)]
+pub struct DiskStats {
+	pub reads: u64,
+	pub bytes_read: u64,
+	pub read_ms: u64,
+	pub writes: u64,
+	pub bytes_written: u64,
+	pub write_ms: u64,
+	/// Time spent with at least one request in flight.
+	pub io_ms: u64,
+	/// Time spent doing I/O weighted by the number of requests in flight.
+	pub weighted_io_ms: u64,
+}
+
+/// Parses the contents of /proc/diskstats into a map of device names to their counters.
+pub fn parse_diskstats(contents: &str) -> HashMap<String, DiskStats> {
+	contents
+		.lines()
+		.filter_map(|line| {
+			let fields: Vec<&str> = line.split_whitespace().collect();
+			if fields.len() < 14 {
+				return None;
+			}
+			let field = |i: usize| fields[i].parse::<u64>().ok();
+			Some((
+				fields[2].to_string(),
+				DiskStats {
+					reads: field(3)?,
+					bytes_read: field(5)? * SECTOR_SIZE,
+					read_ms: field(6)?,
+					writes: field(7)?,
+					bytes_written: field(9)? * SECTOR_SIZE,
+					write_ms: field(10)?,
+					io_ms: field(12)?,
+					weighted_io_ms: field(13)?,
+				},
+			))
+		})
+		.collect()
+}
+
+pub fn read_diskstats() -> io::Result<HashMap<String, DiskStats>> {
+	Ok(parse_diskstats(&fs::read_to_string("/proc/diskstats")?))
+}
+
+/// I/O activity of a block device between two readings of its counters.
+#[derive(Default, Clone, Copy)]
+pub struct IoRates {
+	pub bytes_read: u64,
+	pub bytes_written: u64,
+	pub iops: f64,
+	/// Average time that requests took to be served, including time spent queued.
+	pub await_ms: f64,
+	/// Average number of requests in flight.
+	pub queue_depth: f64,
+	/// Percent of the time that the device was busy.
+	pub utilization: f64,
+}
+
+impl IoRates {
+	pub fn new(previous: &DiskStats, current: &DiskStats, seconds: f64) -> IoRates {
+		let delta = |previous: u64, current: u64| current.saturating_sub(previous) as f64;
+		let ios = delta(previous.reads, current.reads) + delta(previous.writes, current.writes);
+		let io_ms =
+			delta(previous.read_ms, current.read_ms) + delta(previous.write_ms, current.write_ms);
+		let elapsed_ms = seconds * 1000.0;
+
+		IoRates {
+			bytes_read: (delta(previous.bytes_read, current.bytes_read) / seconds) as u64,
+			bytes_written: (delta(previous.bytes_written, current.bytes_written) / seconds) as u64,
+			iops: ios / seconds,
+			await_ms: if ios > 0.0 { io_ms / ios } else { 0.0 },
+			queue_depth: delta(previous.weighted_io_ms, current.weighted_io_ms) / elapsed_ms,
+			// The kernel can count slightly more busy time than has elapsed.
+			utilization: f64::min(
+				delta(previous.io_ms, current.io_ms) / elapsed_ms * 100.0,
+				100.0,
+			),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn it_parses_diskstats() {
+		let diskstats = parse_diskstats(
+			" 259       0 nvme0n1 1000 20 8000 500 2000 40 16000 1500 0 1800 2100 0 0 0 0
+   8       1 sda1 10 0 80 5 20 0 160 15 0 18 21
+",
+		);
+		let nvme = diskstats["nvme0n1"];
+		assert_eq!(nvme.reads, 1000);
+		// Sectors are always 512 bytes.
+		assert_eq!(nvme.bytes_read, 8000 * 512);
+		assert_eq!(nvme.bytes_written, 16000 * 512);
+		assert_eq!(nvme.io_ms, 1800);
+		assert_eq!(nvme.weighted_io_ms, 2100);
+		assert_eq!(diskstats["sda1"].writes, 20);
+	}
+
+	#[test]
+	fn it_computes_io_rates() {
+		let previous = DiskStats {
+			reads: 100,
+			bytes_read: 1000,
+			read_ms: 50,
+			io_ms: 100,
+			..DiskStats::default()
+		};
+		let current = DiskStats {
+			reads: 140,
+			bytes_read: 5000,
+			read_ms: 130,
+			io_ms: 1100,
+			..DiskStats::default()
+		};
+		let rates = IoRates::new(&previous, &current, 2.0);
+		assert_eq!(rates.bytes_read, 2000);
+		assert_eq!(rates.iops, 20.0);
+		assert_eq!(rates.await_ms, 2.0);
+		assert_eq!(rates.utilization, 50.0);
+	}
+}
@@ -0,0 +1,822 @@
+mod blockdev;
+mod diskstats;
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use glob::Pattern;
+use nix::sys::statvfs::statvfs;
+use num_rational::Ratio;
+use psutil::disk;
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Rect};
+use tui::style::{Modifier, Style};
+use tui::widgets::{Block, Row, Table, Widget};
+
+use crate::colorscheme::Colorscheme;
+use crate::history::{linear_trend, History};
+use crate::sparkline::{RenderDirection, Sparkline};
+use crate::units::Units;
+use crate::update::UpdatableWidget;
+use crate::widgets::proc::{SortDirection, DOWN_ARROW, UP_ARROW};
+use crate::widgets::{axis, block, ScrollableTable};
+
+use self::blockdev::BlockDevice;
+use self::diskstats::{DiskStats, IoRates};
+
+// Widths of the table columns after the partition name and mountpoint, which are dropped from the
+// right when there isn't enough room.
+const TABLE_COLUMN_WIDTHS: [u16; 13] = [5, 5, 8, 6, 8, 8, 5, 8, 7, 2, 6, 7, 5];
+
+// Filesystems that are projected to be full within this many seconds are highlighted.
+const FILLING_UP_SECONDS: f64 = 60.0 * 60.0;
+
+// Projections further out than this are mostly noise.
+const MAX_PROJECTION_SECONDS: f64 = 365.0 * 24.0 * 60.0 * 60.0;
+
+// Width of the text shown to the left of each sparkline.
+const SPARKLINE_TEXT_WIDTH: u16 = 20;
+
+// Filesystems whose server can go away, which makes statvfs hang. FUSE filesystems are in the same
+// boat since their daemon can hang, except for fuseblk, which is backed by a local block device.
+const REMOTE_FILESYSTEMS: [&str; 11] = [
+	"nfs",
+	"nfs4",
+	"cifs",
+	"smb3",
+	"smbfs",
+	"9p",
+	"afs",
+	"ceph",
+	"glusterfs",
+	"lustre",
+	"fuse",
+];
+
+fn is_remote_filesystem(filesystem: &str) -> bool {
+	REMOTE_FILESYSTEMS.contains(&filesystem) || filesystem.starts_with("fuse.")
+}
+
+#[derive(PartialEq)]
+enum DiskView {
+	Table,
+	Tree,
+	Sparklines,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum SortMethod {
+	Name,
+	Used,
+	Free,
+	Read,
+	Write,
+}
+
+#[derive(Default)]
+struct IoHistory {
+	bytes_read: History<u64>,
+	bytes_written: History<u64>,
+}
+
+#[derive(Clone)]
+struct Partition {
+	name: String,
+	mountpoint: PathBuf,
+	filesystem: String,
+	read_only: bool,
+	// The partition's block device and the devices it's built on.
+	device: BlockDevice,
+	// `None` until we have two readings of the device's counters.
+	io_rates: Option<IoRates>,
+	used_percent: f32,
+	bytes_free: u64,
+	bytes_total: u64,
+	// `None` for filesystems that don't have a fixed number of inodes, like btrfs.
+	inodes_used_percent: Option<f64>,
+	// `None` unless the free space has been trending down.
+	seconds_until_full: Option<f64>,
+}
+
+pub struct DiskWidget<'a> {
+	title: String,
+	update_interval: Ratio<u64>,
+	colorscheme: &'a Colorscheme,
+	units: Units,
+
+	view: DiskView,
+
+	// Whether the table has the keyboard focus for scrolling and sorting.
+	focused: bool,
+	selected_row: usize,
+	// Key of the selected partition, so that the selection follows it when the table is resorted.
+	selected_partition: Option<String>,
+	sort_method: SortMethod,
+	sort_direction: SortDirection,
+	view_offset: usize,
+	scrolled: bool,
+	view_height: usize,
+
+	// Whether to show virtual filesystems like tmpfs and network mounts.
+	all_filesystems: bool,
+	mount_include: Vec<Pattern>,
+	mount_exclude: Vec<Pattern>,
+
+	// Physical partitions are keyed by name and other filesystems by mountpoint, since many of them
+	// share a device name like `tmpfs`.
+	partitions: HashMap<String, Partition>,
+	// Read and write rates of each partition.
+	io_history: HashMap<String, IoHistory>,
+	// Free bytes of each partition and when they were read, over the last `fill_window` seconds.
+	free_history: HashMap<String, VecDeque<(Instant, u64)>>,
+	fill_window: Duration,
+
+	// The most recent I/O counters of each block device and when they were read.
+	diskstats: Option<(Instant, HashMap<String, DiskStats>)>,
+	// I/O activity of every block device by kernel name, including the ones under partitions.
+	io_rates: HashMap<String, IoRates>,
+}
+
+impl DiskWidget<'_> {
+	pub fn new(
+		colorscheme: &Colorscheme,
+		units: Units,
+		all_filesystems: bool,
+		mount_include: Vec<Pattern>,
+		mount_exclude: Vec<Pattern>,
+		fill_window: u64,
+	) -> DiskWidget {
+		DiskWidget {
+			title: " Disk Usage ".to_string(),
+			update_interval: Ratio::from_integer(1),
+			colorscheme,
+			units,
+
+			view: DiskView::Table,
+
+			focused: false,
+			selected_row: 0,
+			selected_partition: None,
+			sort_method: SortMethod::Name,
+			sort_direction: SortDirection::Up,
+			view_offset: 0,
+			scrolled: false,
+			view_height: 0,
+
+			all_filesystems,
+			mount_include,
+			mount_exclude,
+
+			partitions: HashMap::new(),
+			io_history: HashMap::new(),
+			free_history: HashMap::new(),
+			fill_window: Duration::from_secs(fill_window),
+
+			diskstats: None,
+			io_rates: HashMap::new(),
+		}
+	}
+}
+
+impl DiskWidget<'_> {
+	pub fn cycle_view(&mut self) {
+		self.view = match self.view {
+			DiskView::Table => DiskView::Tree,
+			DiskView::Tree => DiskView::Sparklines,
+			DiskView::Sparklines => DiskView::Table,
+		};
+		// Only the table can be scrolled and sorted.
+		self.focused = false;
+	}
+
+	pub fn toggle_focus(&mut self) {
+		self.focused = !self.focused && self.view == DiskView::Table;
+	}
+
+	pub fn is_focused(&self) -> bool {
+		self.focused
+	}
+
+	fn scroll_count(&mut self, count: isize) {
+		self.selected_row = isize::max(0, self.selected_row as isize + count) as usize;
+		self.selected_partition = None;
+		self.scrolled = true;
+	}
+
+	fn scroll_to(&mut self, count: usize) {
+		self.selected_row = usize::min(count, self.partitions.len().saturating_sub(1));
+		self.selected_partition = None;
+		self.scrolled = true;
+	}
+
+	fn sort(&mut self, sort_method: SortMethod) {
+		if self.sort_method == sort_method {
+			self.sort_direction = !self.sort_direction;
+		} else {
+			self.sort_method = sort_method;
+			// Names read best alphabetically and everything else with the largest values first.
+			self.sort_direction = if sort_method == SortMethod::Name {
+				SortDirection::Up
+			} else {
+				SortDirection::Down
+			};
+		}
+		// Keep the selected partition in view wherever it gets sorted to.
+		self.scrolled = true;
+	}
+
+	pub fn sort_by_name(&mut self) {
+		self.sort(SortMethod::Name);
+	}
+
+	pub fn sort_by_used(&mut self) {
+		self.sort(SortMethod::Used);
+	}
+
+	pub fn sort_by_free(&mut self) {
+		self.sort(SortMethod::Free);
+	}
+
+	pub fn sort_by_read(&mut self) {
+		self.sort(SortMethod::Read);
+	}
+
+	pub fn sort_by_write(&mut self) {
+		self.sort(SortMethod::Write);
+	}
+
+	pub fn toggle_all_filesystems(&mut self) {
+		self.all_filesystems = !self.all_filesystems;
+	}
+
+	fn is_mountpoint_shown(&self, mountpoint: &Path) -> bool {
+		(self.mount_include.is_empty()
+			|| self
+				.mount_include
+				.iter()
+				.any(|pattern| pattern.matches_path(mountpoint)))
+			&& !self
+				.mount_exclude
+				.iter()
+				.any(|pattern| pattern.matches_path(mountpoint))
+	}
+
+	// Reading the usage of a remote filesystem blocks the UI until its server answers, which is
+	// forever if it's gone, so they're only read when they're asked for with `--mount-include`.
+	fn is_filesystem_queried(&self, mountpoint: &Path, filesystem: &str) -> bool {
+		!is_remote_filesystem(filesystem)
+			|| self
+				.mount_include
+				.iter()
+				.any(|pattern| pattern.matches_path(mountpoint))
+	}
+}
+
+impl ScrollableTable for DiskWidget<'_> {
+	fn scroll_up(&mut self) {
+		self.scroll_count(-1);
+	}
+
+	fn scroll_down(&mut self) {
+		self.scroll_count(1);
+	}
+
+	fn scroll_top(&mut self) {
+		self.scroll_to(0);
+	}
+
+	fn scroll_bottom(&mut self) {
+		self.scroll_to(self.partitions.len());
+	}
+
+	fn scroll_half_page_down(&mut self) {
+		self.scroll_count(self.view_height as isize / 2);
+	}
+
+	fn scroll_half_page_up(&mut self) {
+		self.scroll_count(-(self.view_height as isize / 2));
+	}
+
+	fn scroll_full_page_down(&mut self) {
+		self.scroll_count(self.view_height as isize);
+	}
+
+	fn scroll_full_page_up(&mut self) {
+		self.scroll_count(-(self.view_height as isize));
+	}
+}
+
+impl UpdatableWidget for DiskWidget<'_> {
+	fn update(&mut self) {
+		// /proc/diskstats is only available on Linux.
+		let diskstats = diskstats::read_diskstats()
+			.ok()
+			.map(|stats| (Instant::now(), stats));
+		let previous_diskstats = self.diskstats.take();
+		// There are no rates for devices that were added since the last update.
+		self.io_rates = match (&previous_diskstats, &diskstats) {
+			(Some((then, previous)), Some((now, current))) => {
+				let seconds = now.duration_since(*then).as_secs_f64();
+				current
+					.iter()
+					.filter_map(|(name, current)| {
+						let previous = previous.get(name)?;
+						Some((name.clone(), IoRates::new(previous, current, seconds)))
+					})
+					.collect()
+			}
+			_ => HashMap::new(),
+		};
+		let partitions = if self.all_filesystems {
+			disk::partitions()
+		} else {
+			disk::partitions_physical()
+		};
+		let io_rates = &self.io_rates;
+		// `.rev()` selects the correct mountpoint when the partition is mounted multiple times
+		// https://github.com/cjbassi/ytop/issues/25
+		let partitions: HashMap<String, Partition> = partitions
+			.unwrap()
+			.into_iter()
+			.rev()
+			.filter(|partition| {
+				self.is_mountpoint_shown(partition.mountpoint())
+					&& self.is_filesystem_queried(
+						partition.mountpoint(),
+						partition.filesystem().as_str(),
+					)
+			})
+			.map(|partition| {
+				let name = PathBuf::from(partition.device())
+					.file_name()
+					.map(|name| name.to_string_lossy().to_string())
+					.unwrap_or_else(|| partition.device().to_string());
+				let mountpoint = partition.mountpoint().to_path_buf();
+				let filesystem = partition.filesystem();
+				let read_only = partition
+					.mount_options()
+					.split(',')
+					.any(|option| option == "ro");
+
+				// We use `unwrap_or_default` since the function may return an error if there is
+				// insufficient permissions to read the disk usage of the partition.
+				// https://github.com/cjbassi/ytop/issues/48
+				let disk_usage = disk::disk_usage(&mountpoint).unwrap_or_default();
+				// Devices like /dev/mapper/root are symlinks to the name that the kernel uses in
+				// /proc/diskstats, e.g. dm-0.
+				let device_name = fs::canonicalize(partition.device())
+					.ok()
+					.and_then(|path| {
+						path.file_name()
+							.map(|name| name.to_string_lossy().to_string())
+					})
+					.unwrap_or_else(|| name.clone());
+				let device = BlockDevice::stack(&device_name);
+				let io_rates = io_rates.get(&device_name).cloned();
+				let used_percent = disk_usage.percent();
+				let bytes_free = disk_usage.free();
+				let bytes_total = disk_usage.total();
+				let inodes_used_percent = statvfs(&mountpoint)
+					.ok()
+					.filter(|stats| stats.files() > 0)
+					.map(|stats| {
+						(stats.files() - stats.files_free()) as f64 / stats.files() as f64 * 100.0
+					});
+
+				(
+					if filesystem.is_physical() {
+						name.clone()
+					} else {
+						format!("{}", mountpoint.display())
+					},
+					Partition {
+						name,
+						mountpoint,
+						filesystem: filesystem.as_str().to_string(),
+						read_only,
+						device,
+						io_rates,
+						used_percent,
+						bytes_free,
+						bytes_total,
+						inodes_used_percent,
+						seconds_until_full: None,
+					},
+				)
+			})
+			// Like `df`, skip pseudo filesystems like proc and cgroup that don't have any space.
+			.filter(|(_key, partition)| partition.bytes_total > 0)
+			.collect();
+		self.partitions = partitions;
+
+		self.diskstats = diskstats;
+
+		let partitions = &self.partitions;
+		self.io_history
+			.retain(|name, _history| partitions.contains_key(name));
+		for (name, partition) in &self.partitions {
+			if let Some(io_rates) = partition.io_rates {
+				let history = self.io_history.entry(name.clone()).or_default();
+				history.bytes_read.push(io_rates.bytes_read);
+				history.bytes_written.push(io_rates.bytes_written);
+			}
+		}
+
+		let now = Instant::now();
+		let fill_window = self.fill_window;
+		let partitions = &self.partitions;
+		self.free_history
+			.retain(|key, _history| partitions.contains_key(key));
+		for (key, partition) in self.partitions.iter_mut() {
+			let history = self.free_history.entry(key.clone()).or_default();
+			history.push_back((now, partition.bytes_free));
+			// Pruned by age rather than count so that the window stays the same when updates are
+			// skipped, e.g. while paused.
+			while history
+				.front()
+				.filter(|(then, _free)| now.duration_since(*then) > fill_window)
+				.is_some()
+			{
+				history.pop_front();
+			}
+			let points: Vec<(f64, f64)> = history
+				.iter()
+				.map(|(then, free)| (-now.duration_since(*then).as_secs_f64(), *free as f64))
+				.collect();
+			partition.seconds_until_full = linear_trend(&points)
+				.filter(|slope| *slope < 0.0)
+				.map(|slope| partition.bytes_free as f64 / -slope)
+				.filter(|seconds| *seconds < MAX_PROJECTION_SECONDS);
+		}
+	}
+
+	fn get_update_interval(&self) -> Ratio<u64> {
+		self.update_interval
+	}
+}
+
+fn format_time_until_full(seconds: f64) -> String {
+	let days = seconds / (24.0 * 60.0 * 60.0);
+	if days >= 1.0 {
+		format!("{:.0}d", days)
+	} else {
+		axis::format_duration(seconds)
+	}
+}
+
+// TODO: this is only a temporary workaround until we fix the table column resizing
+// https://github.com/cjbassi/ytop/issues/23
+fn custom_column_sizing(width: u16, column_widths: &[u16]) -> Vec<Constraint> {
+	let width = width.saturating_sub(2);
+	// The partition name and mountpoint get at least 5 columns each.
+	let mut columns = column_widths.len();
+	while columns > 0
+		&& width < 10 + column_widths[..columns].iter().sum::<u16>() + columns as u16 + 1
+	{
+		columns -= 1;
+	}
+	if columns == 0 {
+		return vec![Constraint::Min(5), Constraint::Min(5)];
+	}
+	let name_width =
+		(width - column_widths[..columns].iter().sum::<u16>() - columns as u16 - 1) / 2;
+	let mut constraints = vec![
+		Constraint::Length(name_width),
+		Constraint::Length(name_width),
+	];
+	constraints.extend(
+		column_widths[..columns]
+			.iter()
+			.map(|width| Constraint::Length(*width)),
+	);
+	constraints
+}
+
+impl DiskWidget<'_> {
+	fn render_sparklines(&self, area: Rect, buf: &mut Buffer) {
+		self.block(&self.title).render(area, buf);
+
+		if area.height < 3 || area.width < 3 {
+			return;
+		}
+
+		let inner = Rect {
+			x: area.x + 1,
+			y: area.y + 1,
+			width: area.width - 2,
+			height: area.height - 2,
+		};
+
+		let mut names: Vec<&String> = self.io_history.keys().collect();
+		names.sort();
+
+		// Each partition gets a read and a write sparkline of at least one row.
+		let row_height = u16::max(inner.height / u16::max(names.len() as u16 * 2, 1), 1);
+		let rows = names.into_iter().flat_map(|name| {
+			let history = &self.io_history[name];
+			// Scale both sparklines of a partition the same so that they can be compared.
+			let max = history
+				.bytes_read
+				.as_slice()
+				.iter()
+				.chain(history.bytes_written.as_slice())
+				.max()
+				.cloned()
+				.unwrap_or_default();
+			vec![
+				(name.as_str(), "R", &history.bytes_read, max),
+				("", "W", &history.bytes_written, max),
+			]
+		});
+		for (i, (name, label, bytes, max)) in rows.enumerate() {
+			let y = inner.y + i as u16 * row_height;
+			if y + row_height > inner.bottom() {
+				break;
+			}
+			buf.set_stringn(
+				inner.x,
+				y,
+				format!(
+					"{:<7} {} {:>8}/s",
+					name,
+					label,
+					self.units.format(bytes.last().cloned().unwrap_or_default())
+				),
+				SPARKLINE_TEXT_WIDTH as usize,
+				self.colorscheme.text.modifier(Modifier::BOLD),
+			);
+			Sparkline::default()
+				.data(bytes.as_slice())
+				.direction(RenderDirection::RTL)
+				.max(max)
+				.style(self.colorscheme.net_bars)
+				.render(
+					Rect {
+						x: inner.x + SPARKLINE_TEXT_WIDTH + 1,
+						y,
+						width: inner.width.saturating_sub(SPARKLINE_TEXT_WIDTH + 1),
+						height: row_height,
+					},
+					buf,
+				);
+		}
+	}
+
+	fn render_tree(&self, area: Rect, buf: &mut Buffer) {
+		let mut partitions: Vec<&Partition> = self.partitions.values().collect();
+		partitions.sort_by(|a, b| a.name.cmp(&b.name));
+
+		let rows = partitions.into_iter().flat_map(|partition| {
+			partition
+				.device
+				.flatten()
+				.into_iter()
+				.map(|(depth, device)| {
+					let (name, mountpoint) = if depth == 0 {
+						(
+							partition.name.clone(),
+							format!("{}", partition.mountpoint.display()),
+						)
+					} else {
+						let name = match &device.label {
+							Some(label) => format!("{} ({})", device.name, label),
+							None => device.name.clone(),
+						};
+						(
+							format!("{}└─{}", " ".repeat(depth - 1), name),
+							String::new(),
+						)
+					};
+					let mut row = vec![name, mountpoint];
+					row.extend(match self.io_rates.get(&device.name) {
+						Some(io_rates) => vec![
+							self.units.format(io_rates.bytes_read),
+							self.units.format(io_rates.bytes_written),
+							format!("{:3.0}%", io_rates.utilization),
+						],
+						None => vec![String::new(); 3],
+					});
+					row
+				})
+				.collect::<Vec<_>>()
+		});
+
+		// width - (border width) - (column3 + column4 + column5 width) - (spaces between columns)
+		let name_width = area.width.saturating_sub(2 + 8 + 8 + 5 + 4) / 2;
+		Table::new(
+			["Device", "Mount", "R/s", "W/s", "Util"].iter(),
+			rows.map(|row| Row::StyledData(row.into_iter(), self.colorscheme.text)),
+		)
+		.block(self.block(&self.title))
+		.header_style(self.colorscheme.text.modifier(Modifier::BOLD))
+		.widths(&[
+			Constraint::Length(name_width),
+			Constraint::Length(name_width),
+			Constraint::Length(8),
+			Constraint::Length(8),
+			Constraint::Length(5),
+		])
+		.column_spacing(1)
+		.header_gap(0)
+		.render(area, buf);
+	}
+
+	fn block<'t>(&self, title: &'t str) -> Block<'t> {
+		let block = block::new(self.colorscheme, title);
+		if self.focused {
+			block.border_style(Style::default().fg(self.colorscheme.proc_cursor))
+		} else {
+			block
+		}
+	}
+
+	fn render_table(&mut self, area: Rect, buf: &mut Buffer) {
+		if area.height < 3 || area.width < 3 {
+			return;
+		}
+
+		let inner = Rect {
+			x: area.x + 1,
+			y: area.y + 1,
+			width: area.width - 2,
+			height: area.height - 2,
+		};
+
+		self.view_height = inner.height as usize - 1;
+
+		let mut partitions: Vec<(String, Partition)> = self
+			.partitions
+			.iter()
+			.map(|(key, partition)| (key.clone(), partition.clone()))
+			.collect();
+		let sort_method = self.sort_method;
+		let compare = |a: &Partition, b: &Partition| -> Ordering {
+			let by_name = a
+				.name
+				.cmp(&b.name)
+				.then_with(|| a.mountpoint.cmp(&b.mountpoint));
+			let ordering = match sort_method {
+				SortMethod::Name => Ordering::Equal,
+				SortMethod::Used => a
+					.used_percent
+					.partial_cmp(&b.used_percent)
+					.unwrap_or(Ordering::Equal),
+				SortMethod::Free => a.bytes_free.cmp(&b.bytes_free),
+				SortMethod::Read => a
+					.io_rates
+					.map(|io_rates| io_rates.bytes_read)
+					.cmp(&b.io_rates.map(|io_rates| io_rates.bytes_read)),
+				SortMethod::Write => a
+					.io_rates
+					.map(|io_rates| io_rates.bytes_written)
+					.cmp(&b.io_rates.map(|io_rates| io_rates.bytes_written)),
+			};
+			ordering.then(by_name)
+		};
+		if self.sort_direction == SortDirection::Up {
+			partitions.sort_by(|(_, a), (_, b)| compare(a, b));
+		} else {
+			partitions.sort_by(|(_, b), (_, a)| compare(a, b));
+		}
+
+		let mut header = [
+			"Partition",
+			"Mount",
+			"Used",
+			"Inode",
+			"Free",
+			"Full",
+			"R/s",
+			"W/s",
+			"Util",
+			"Size",
+			"Type",
+			"Rw",
+			"IOPS",
+			"Await",
+			"Queue",
+		];
+		let header_index = match self.sort_method {
+			SortMethod::Name => 0,
+			SortMethod::Used => 2,
+			SortMethod::Free => 4,
+			SortMethod::Read => 6,
+			SortMethod::Write => 7,
+		};
+		let arrow = match self.sort_direction {
+			SortDirection::Up => UP_ARROW,
+			SortDirection::Down => DOWN_ARROW,
+		};
+		let updated_header = format!("{}{}", header[header_index], arrow);
+		header[header_index] = &updated_header;
+
+		self.selected_row = match &self.selected_partition {
+			Some(selected_partition) => partitions
+				.iter()
+				.position(|(key, _partition)| key == selected_partition)
+				.unwrap_or(self.selected_row),
+			None => self.selected_row,
+		};
+		self.selected_row = usize::min(self.selected_row, partitions.len().saturating_sub(1));
+		self.selected_partition = partitions
+			.get(self.selected_row)
+			.map(|(key, _partition)| key.clone());
+
+		if self.scrolled {
+			self.scrolled = false;
+			if self.selected_row > (inner.height as usize + self.view_offset).saturating_sub(2) {
+				self.view_offset = self.selected_row + 2 - inner.height as usize;
+			} else if self.selected_row < self.view_offset {
+				self.view_offset = self.selected_row;
+			}
+		}
+		// The table can shrink when filesystems are unmounted or hidden.
+		self.view_offset = usize::min(
+			self.view_offset,
+			partitions.len().saturating_sub(self.view_height),
+		);
+
+		let partitions_count = partitions.len();
+		let title = format!(
+			" Disk Usage ({}-{} of {}) ",
+			usize::min(self.view_offset + 1, partitions_count),
+			usize::min(self.view_offset + self.view_height, partitions_count),
+			partitions_count
+		);
+		Table::new(
+			header.iter(),
+			partitions
+				.into_iter()
+				.skip(self.view_offset)
+				.map(|(_key, partition)| {
+					let style = match partition.seconds_until_full {
+						Some(seconds) if seconds < FILLING_UP_SECONDS => self.colorscheme.temp_high,
+						_ => self.colorscheme.text,
+					};
+					let io_rates = partition.io_rates;
+					let io_rate = |format: &dyn Fn(&IoRates) -> String| {
+						io_rates.as_ref().map(format).unwrap_or_default()
+					};
+					let row = vec![
+						partition.name,
+						format!("{}", partition.mountpoint.display()),
+						format!("{:3.0}%", partition.used_percent),
+						partition
+							.inodes_used_percent
+							.map(|percent| format!("{:3.0}%", percent))
+							.unwrap_or_default(),
+						self.units.format(partition.bytes_free),
+						partition
+							.seconds_until_full
+							.map(format_time_until_full)
+							.unwrap_or_default(),
+						io_rate(&|io_rates| self.units.format(io_rates.bytes_read)),
+						io_rate(&|io_rates| self.units.format(io_rates.bytes_written)),
+						io_rate(&|io_rates| format!("{:3.0}%", io_rates.utilization)),
+						self.units.format(partition.bytes_total),
+						partition.filesystem,
+						if partition.read_only { "ro" } else { "rw" }.to_string(),
+						io_rate(&|io_rates| format!("{:.0}", io_rates.iops)),
+						io_rate(&|io_rates| format!("{:.1}ms", io_rates.await_ms)),
+						io_rate(&|io_rates| format!("{:.2}", io_rates.queue_depth)),
+					];
+					Row::StyledData(row.into_iter(), style)
+				}),
+		)
+		.block(self.block(&title))
+		.header_style(self.colorscheme.text.modifier(Modifier::BOLD))
+		.widths(&custom_column_sizing(area.width, &TABLE_COLUMN_WIDTHS))
+		.column_spacing(1)
+		.header_gap(0)
+		.render(area, buf);
+
+		// Draw cursor.
+		if self.focused && partitions_count > 0 {
+			let cursor_y = inner.y + 1 + self.selected_row as u16 - self.view_offset as u16;
+			if cursor_y < inner.bottom() {
+				for i in inner.x..inner.right() {
+					let cell = buf.get_mut(i, cursor_y);
+					if cell.symbol != " " {
+						cell.set_modifier(Modifier::REVERSED);
+						cell.set_fg(self.colorscheme.proc_cursor);
+					} else {
+						cell.set_bg(self.colorscheme.proc_cursor);
+					}
+				}
+			}
+		}
+	}
+}
+
+impl Widget for &mut DiskWidget<'_> {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		match self.view {
+			DiskView::Table => self.render_table(area, buf),
+			DiskView::Tree => self.render_tree(area, buf),
+			DiskView::Sparklines => self.render_sparklines(area, buf),
+		}
+	}
+}
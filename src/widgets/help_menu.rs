@@ -32,12 +32,37 @@ Process filtering:
   - (while editing):
     - <Enter>: accept filter
     - <C-c> and <Escape>: clear filter
-CPU and Mem graph scaling:
+CPU legend:
+  - [ and ]: select previous/next line
+  - v and <click>: hide/show selected line
+  - o: show only selected line (again for all)
+  - <Escape>: clear selection
+CPU, Mem, and PSI graph scaling:
   - h: scale in
-  - l: scale out";
+  - l: scale out
+Mem views:
+  - M: cycle basic, detailed, and paging views
+  - N: toggle per-NUMA-node usage (basic view)
+Disk views:
+  - D: cycle table, tree, and I/O sparklines
+  - F: toggle showing all filesystems
+  - f: focus the disk table to scroll it with
+    the process navigation keys and sort by:
+    - n: Name
+    - u: Used
+    - e: Free
+    - r: R/s
+    - w: W/s
+Net:
+  - i: cycle interface shown in the graphs
+  - I: toggle table of all interfaces
+  - x: cycle graph scale (max, log, link)
+CPU, Mem, and Net graph statistics:
+  - s: toggle min/max/avg/p95 of visible data
+  - S: toggle average lines";
 
 const TEXT_WIDTH: u16 = 48;
-const TEXT_HEIGHT: u16 = 29;
+const TEXT_HEIGHT: u16 = 54;
 
 static TEXT_VEC: Lazy<Vec<Text<'static>>> = Lazy::new(|| {
 	TEXT.lines()
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+
+/// Parses the contents of /proc/meminfo into a map of field names to their sizes in bytes.
+pub fn parse_meminfo(contents: &str) -> HashMap<String, u64> {
+	contents
+		.lines()
+		.filter_map(|line| parse_meminfo_fields(line.split_whitespace()))
+		.collect()
+}
+
+/// Parses the fields of a line like `MemTotal:  6147400 kB` into the name and size in bytes.
+pub fn parse_meminfo_fields<'a, I: Iterator<Item = &'a str>>(
+	mut fields: I,
+) -> Option<(String, u64)> {
+	let key = fields.next()?.trim_end_matches(':');
+	let value: u64 = fields.next()?.parse().ok()?;
+	let value = match fields.next() {
+		Some("kB") => value * 1024,
+		_ => value,
+	};
+	Some((key.to_string(), value))
+}
+
+pub fn read_meminfo() -> io::Result<HashMap<String, u64>> {
+	Ok(parse_meminfo(&fs::read_to_string("/proc/meminfo")?))
+}
+
+/// Where the system's memory is going, with every field except `available` adding up to `total`.
+#[derive(Default, Clone, Copy)]
+pub struct MemBreakdown {
+	pub total: u64,
+	/// Memory used by processes and the kernel that can't be reclaimed.
+	pub used: u64,
+	pub buffers: u64,
+	/// The page cache, not including shared memory.
+	pub cache: u64,
+	/// tmpfs and shared memory, which live in the page cache but can't simply be dropped.
+	pub shared: u64,
+	pub slab: u64,
+	pub free: u64,
+	/// The kernel's estimate of how much memory can be made available without swapping.
+	pub available: u64,
+}
+
+impl MemBreakdown {
+	pub fn from_meminfo(meminfo: &HashMap<String, u64>) -> MemBreakdown {
+		let field = |key: &str| meminfo.get(key).cloned().unwrap_or_default();
+
+		let total = field("MemTotal");
+		let free = field("MemFree");
+		let buffers = field("Buffers");
+		let cached = field("Cached");
+		let shared = u64::min(field("Shmem"), cached);
+		let slab = field("Slab");
+		let used = total.saturating_sub(free + buffers + cached + slab);
+
+		MemBreakdown {
+			total,
+			used,
+			buffers,
+			cache: cached - shared,
+			shared,
+			slab,
+			free,
+			available: field("MemAvailable"),
+		}
+	}
+}
+
+/// Persistent huge pages, which are reserved up front for hugetlbfs, and transparent huge pages.
+#[derive(Default, Clone, Copy)]
+pub struct HugePages {
+	pub total: u64,
+	pub free: u64,
+	/// Pages that have been promised to a mapping but not faulted in yet.
+	pub reserved: u64,
+	pub page_size: u64,
+	/// Bytes of anonymous, shmem, and file memory backed by transparent huge pages.
+	pub transparent: u64,
+}
+
+impl HugePages {
+	pub fn from_meminfo(meminfo: &HashMap<String, u64>) -> HugePages {
+		let field = |key: &str| meminfo.get(key).cloned().unwrap_or_default();
+
+		HugePages {
+			total: field("HugePages_Total"),
+			free: field("HugePages_Free"),
+			reserved: field("HugePages_Rsvd"),
+			page_size: field("Hugepagesize"),
+			transparent: field("AnonHugePages") + field("ShmemHugePages") + field("FileHugePages"),
+		}
+	}
+}
+
+/// Pages that zswap has compressed on their way to swap.
+#[derive(Clone, Copy)]
+pub struct Zswap {
+	pub stored: u64,
+	pub compressed: u64,
+}
+
+impl Zswap {
+	/// Returns `None` on kernels that don't report zswap in /proc/meminfo.
+	pub fn from_meminfo(meminfo: &HashMap<String, u64>) -> Option<Zswap> {
+		Some(Zswap {
+			stored: *meminfo.get("Zswapped")?,
+			compressed: *meminfo.get("Zswap")?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const MEMINFO: &str = "MemTotal:        8000 kB
+MemFree:         1000 kB
+MemAvailable:    5000 kB
+Buffers:          500 kB
+Cached:          3000 kB
+Shmem:            200 kB
+Slab:             400 kB
+HugePages_Total:      4
+HugePages_Free:       1
+HugePages_Rsvd:       2
+Hugepagesize:       2048 kB
+AnonHugePages:    100 kB
+ShmemHugePages:    20 kB
+";
+
+	#[test]
+	fn it_parses_meminfo_in_bytes() {
+		let meminfo = parse_meminfo(MEMINFO);
+		assert_eq!(meminfo.get("MemTotal"), Some(&(8000 * 1024)));
+		// Page counts don't have a unit and aren't scaled.
+		assert_eq!(meminfo.get("HugePages_Total"), Some(&4));
+	}
+
+	#[test]
+	fn it_reads_huge_pages() {
+		let meminfo = parse_meminfo(MEMINFO);
+		let huge_pages = HugePages::from_meminfo(&meminfo);
+		assert_eq!(huge_pages.total, 4);
+		assert_eq!(huge_pages.free, 1);
+		assert_eq!(huge_pages.reserved, 2);
+		assert_eq!(huge_pages.page_size, 2048 * 1024);
+		assert_eq!(huge_pages.transparent, 120 * 1024);
+		// Older kernels don't report zswap.
+		assert!(Zswap::from_meminfo(&meminfo).is_none());
+	}
+
+	#[test]
+	fn it_breaks_down_memory() {
+		let breakdown = MemBreakdown::from_meminfo(&parse_meminfo(MEMINFO));
+		assert_eq!(breakdown.used, 3100 * 1024);
+		assert_eq!(breakdown.cache, 2800 * 1024);
+		assert_eq!(breakdown.shared, 200 * 1024);
+		assert_eq!(
+			breakdown.used
+				+ breakdown.buffers
+				+ breakdown.cache
+				+ breakdown.shared
+				+ breakdown.slab
+				+ breakdown.free,
+			breakdown.total
+		);
+	}
+}
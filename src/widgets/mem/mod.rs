@@ -0,0 +1,691 @@
+mod meminfo;
+mod numa;
+mod vmstat;
+mod zram;
+
+use std::time::Instant;
+
+use num_rational::Ratio;
+use psutil::memory;
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Rect};
+use tui::style::{Modifier, Style};
+use tui::symbols::Marker;
+use tui::widgets::{Axis, Chart, Dataset, GraphType, Row, Table, Widget};
+
+use crate::colorscheme::Colorscheme;
+use crate::history::{History, Stats, MAX_HORIZONTAL_SCALE};
+use crate::sparkline::{RenderDirection, Sparkline};
+use crate::units::Units;
+use crate::update::UpdatableWidget;
+use crate::widgets::{axis, block};
+
+use self::meminfo::{HugePages, MemBreakdown, Zswap};
+use self::vmstat::SwapDevice;
+use self::zram::ZramDevice;
+
+const HORIZONTAL_SCALE_DELTA: u64 = 25;
+
+#[derive(PartialEq)]
+enum MemView {
+	Basic,
+	Detailed,
+	Paging,
+}
+
+const BREAKDOWN_LABELS: [&str; 6] = ["Used", "Buffers", "Cache", "Shared", "Slab", "Free"];
+
+const PAGING_LABELS: [&str; 4] = ["Swap in", "Swap out", "Major faults", "Minor faults"];
+
+// Width of the text shown to the left of each paging sparkline, e.g. "Major faults    1234/s".
+const PAGING_TEXT_WIDTH: u16 = 22;
+
+#[derive(Default)]
+struct MemData {
+	total: u64,
+	used: u64,
+	// Only known for NUMA nodes, where it's shown in the legend.
+	free: Option<u64>,
+	percents: History<(f64, f64)>,
+}
+
+pub struct MemWidget<'a> {
+	title: String,
+	update_interval: Ratio<u64>,
+	colorscheme: &'a Colorscheme,
+	units: Units,
+
+	horizontal_scale: u64,
+
+	update_count: u64,
+
+	show_stats: bool,
+	show_mean_lines: bool,
+
+	view: MemView,
+
+	main: MemData,
+	swap: Option<MemData>,
+
+	breakdown: MemBreakdown,
+	huge_pages: HugePages,
+	zswap: Option<Zswap>,
+	zram_devices: Vec<ZramDevice>,
+	// Percentages of total memory for each of the `BREAKDOWN_LABELS`.
+	breakdown_percents: Vec<History<(f64, f64)>>,
+
+	// The most recent values of the counters for each of the `PAGING_LABELS` and when they were
+	// read, so that we can calculate rates.
+	paging_counters: Option<(Instant, [u64; 4])>,
+	paging_rates: Vec<History<u64>>,
+	swap_devices: Vec<SwapDevice>,
+
+	show_numa_nodes: bool,
+	// Usage of each NUMA node by node id.
+	numa_nodes: Vec<(u32, MemData)>,
+}
+
+impl MemWidget<'_> {
+	pub fn new(colorscheme: &Colorscheme, units: Units, update_interval: Ratio<u64>) -> MemWidget {
+		let update_count = 0;
+
+		let mut main = MemData::default();
+		main.percents.push((update_count as f64, 0.0));
+
+		MemWidget {
+			title: " Memory Usage ".to_string(),
+			update_interval,
+			colorscheme,
+			units,
+
+			horizontal_scale: 100,
+
+			update_count,
+
+			show_stats: false,
+			show_mean_lines: false,
+
+			view: MemView::Basic,
+
+			main,
+			swap: None,
+
+			breakdown: MemBreakdown::default(),
+			huge_pages: HugePages::default(),
+			zswap: None,
+			zram_devices: Vec::new(),
+			breakdown_percents: BREAKDOWN_LABELS
+				.iter()
+				.map(|_| History::default())
+				.collect(),
+
+			paging_counters: None,
+			paging_rates: PAGING_LABELS.iter().map(|_| History::default()).collect(),
+			swap_devices: Vec::new(),
+
+			show_numa_nodes: false,
+			numa_nodes: Vec::new(),
+		}
+	}
+
+	pub fn cycle_view(&mut self) {
+		self.view = match self.view {
+			// The detailed views are read from /proc.
+			MemView::Basic if cfg!(target_os = "linux") => MemView::Detailed,
+			MemView::Detailed => MemView::Paging,
+			_ => MemView::Basic,
+		};
+	}
+
+	pub fn toggle_numa_nodes(&mut self) {
+		self.show_numa_nodes = !self.show_numa_nodes;
+	}
+
+	pub fn toggle_stats(&mut self) {
+		self.show_stats = !self.show_stats;
+	}
+
+	pub fn toggle_mean_lines(&mut self) {
+		self.show_mean_lines = !self.show_mean_lines;
+	}
+
+	pub fn scale_in(&mut self) {
+		if self.horizontal_scale > HORIZONTAL_SCALE_DELTA {
+			self.horizontal_scale -= HORIZONTAL_SCALE_DELTA;
+		}
+	}
+
+	pub fn scale_out(&mut self) {
+		if self.horizontal_scale + HORIZONTAL_SCALE_DELTA <= MAX_HORIZONTAL_SCALE {
+			self.horizontal_scale += HORIZONTAL_SCALE_DELTA;
+		}
+	}
+}
+
+impl UpdatableWidget for MemWidget<'_> {
+	fn update(&mut self) {
+		self.update_count += 1;
+
+		let main = memory::virtual_memory().unwrap();
+		let swap = memory::swap_memory().unwrap();
+
+		self.main.total = main.total();
+		self.main.used = main.used();
+		self.main
+			.percents
+			.push((self.update_count as f64, main.percent().into()));
+
+		if let Ok(meminfo) = meminfo::read_meminfo() {
+			self.breakdown = MemBreakdown::from_meminfo(&meminfo);
+			self.huge_pages = HugePages::from_meminfo(&meminfo);
+			self.zswap = Zswap::from_meminfo(&meminfo);
+			let breakdown = &self.breakdown;
+			let bytes = [
+				breakdown.used,
+				breakdown.buffers,
+				breakdown.cache,
+				breakdown.shared,
+				breakdown.slab,
+				breakdown.free,
+			];
+			for (percents, bytes) in self.breakdown_percents.iter_mut().zip(bytes.iter()) {
+				percents.push((
+					self.update_count as f64,
+					*bytes as f64 / u64::max(breakdown.total, 1) as f64 * 100.0,
+				));
+			}
+		}
+
+		if let Ok(vmstat) = vmstat::read_vmstat() {
+			let counter = |key: &str| vmstat.get(key).cloned().unwrap_or_default();
+			let counters = [
+				counter("pswpin"),
+				counter("pswpout"),
+				counter("pgmajfault"),
+				// `pgfault` counts both minor and major faults.
+				counter("pgfault").saturating_sub(counter("pgmajfault")),
+			];
+			let now = Instant::now();
+			if let Some((then, previous_counters)) = self.paging_counters {
+				let seconds = now.duration_since(then).as_secs_f64();
+				for ((rates, current), previous) in self
+					.paging_rates
+					.iter_mut()
+					.zip(counters.iter())
+					.zip(previous_counters.iter())
+				{
+					rates.push((current.saturating_sub(*previous) as f64 / seconds) as u64);
+				}
+			}
+			self.paging_counters = Some((now, counters));
+		}
+		self.swap_devices = vmstat::read_swaps().unwrap_or_default();
+		self.zram_devices = zram::read_zram_devices().unwrap_or_default();
+
+		if let Ok(nodes) = numa::read_numa_nodes() {
+			// Nodes only change when memory is hotplugged, in which case we start over.
+			if nodes.len() != self.numa_nodes.len()
+				|| nodes
+					.iter()
+					.zip(&self.numa_nodes)
+					.any(|(node, (id, _data))| node.id != *id)
+			{
+				self.numa_nodes = nodes
+					.iter()
+					.map(|node| (node.id, MemData::default()))
+					.collect();
+			}
+			for (node, (_id, data)) in nodes.iter().zip(self.numa_nodes.iter_mut()) {
+				data.total = node.total;
+				data.used = node.used;
+				data.free = Some(node.free);
+				data.percents.push((
+					self.update_count as f64,
+					node.used as f64 / u64::max(node.total, 1) as f64 * 100.0,
+				));
+			}
+		}
+
+		if swap.total() == 0 {
+			self.swap = None;
+		} else {
+			if self.swap.is_none() {
+				self.swap = Some(MemData::default());
+				self.swap
+					.as_mut()
+					.unwrap()
+					.percents
+					.push((self.update_count as f64 - 1.0, 0.0));
+			}
+			self.swap.as_mut().unwrap().total = swap.total();
+			self.swap.as_mut().unwrap().used = swap.used();
+			self.swap
+				.as_mut()
+				.unwrap()
+				.percents
+				.push((self.update_count as f64, swap.percent().into()));
+		}
+	}
+
+	fn get_update_interval(&self) -> Ratio<u64> {
+		self.update_interval
+	}
+}
+
+impl MemWidget<'_> {
+	fn render_basic(&self, area: Rect, buf: &mut Buffer) {
+		let mut series = vec![(
+			"Main".to_string(),
+			self.colorscheme.mem_main,
+			&self.main,
+			None,
+		)];
+		if let Some(swap) = &self.swap {
+			series.push((
+				"Swap".to_string(),
+				self.colorscheme.mem_swap,
+				swap,
+				self.compressed_swap_note(),
+			));
+		}
+		if self.show_numa_nodes {
+			for (i, (id, data)) in self.numa_nodes.iter().enumerate() {
+				series.push((
+					format!("Node {}", id),
+					self.colorscheme.cpu_lines[i % self.colorscheme.cpu_lines.len()],
+					data,
+					data.free
+						.map(|free| format!("{} free", self.units.format(free))),
+				));
+			}
+		}
+
+		let x_bounds = [
+			self.update_count as f64 - self.horizontal_scale as f64,
+			self.update_count as f64 + 1.0,
+		];
+		let x_labels = axis::time_labels(self.horizontal_scale, self.update_interval);
+		let y_labels = axis::percent_labels(100.0);
+		let graph_area = axis::graph_area(area, &x_labels, &y_labels);
+
+		let stats: Vec<Option<Stats>> = series
+			.iter()
+			.map(|(_label, _style, data, _note)| {
+				Stats::of_window(data.percents.as_slice(), x_bounds[0])
+			})
+			.collect();
+		let mean_lines: Vec<Vec<(f64, f64)>> = stats
+			.iter()
+			.map(|stats| match stats {
+				Some(stats) if self.show_mean_lines => {
+					axis::dashed_line(stats.mean, x_bounds, graph_area)
+				}
+				_ => Vec::new(),
+			})
+			.collect();
+
+		let mut datasets = Vec::new();
+		for (i, (_label, style, data, _note)) in series.iter().enumerate() {
+			datasets.push(
+				Dataset::default()
+					.marker(Marker::Braille)
+					.graph_type(GraphType::Line)
+					.style(*style)
+					.data(data.percents.as_slice()),
+			);
+			if !mean_lines[i].is_empty() {
+				datasets.push(
+					Dataset::default()
+						.marker(Marker::Braille)
+						.graph_type(GraphType::Scatter)
+						.style(*style)
+						.data(&mean_lines[i]),
+				);
+			}
+		}
+
+		Chart::<String, String>::default()
+			.block(block::new(self.colorscheme, &self.title))
+			.x_axis(
+				Axis::default()
+					.bounds(x_bounds)
+					.labels(&x_labels)
+					.style(self.colorscheme.borders)
+					.labels_style(self.colorscheme.text),
+			)
+			.y_axis(
+				Axis::default()
+					.bounds([0.0, 100.0])
+					.labels(&y_labels)
+					.style(self.colorscheme.borders)
+					.labels_style(self.colorscheme.text),
+			)
+			.datasets(&datasets)
+			.render(area, buf);
+
+		let label_width = series
+			.iter()
+			.map(|(label, _style, _data, _note)| label.len())
+			.max()
+			.unwrap_or_default();
+		for (i, (label, style, data, note)) in series.iter().enumerate() {
+			let y = graph_area.y + 1 + i as u16;
+			if y >= graph_area.bottom() {
+				break;
+			}
+			let mut text = format!(
+				"{:<width$} {:3.0}% {}/{}",
+				label,
+				data.percents.last().unwrap().1,
+				self.units.format(data.used),
+				self.units.format(data.total),
+				width = label_width,
+			);
+			if let Some(note) = note {
+				text = format!("{} {}", text, note);
+			}
+			if let (true, Some(stats)) = (self.show_stats, &stats[i]) {
+				text = format!("{}  {}", text, axis::percent_stats_label(stats));
+			}
+			buf.set_stringn(
+				graph_area.x + 1,
+				y,
+				text,
+				graph_area.width.saturating_sub(1) as usize,
+				*style,
+			);
+		}
+	}
+
+	fn render_detailed(&self, area: Rect, buf: &mut Buffer) {
+		let x_bounds = [
+			self.update_count as f64 - self.horizontal_scale as f64,
+			self.update_count as f64 + 1.0,
+		];
+		let x_labels = axis::time_labels(self.horizontal_scale, self.update_interval);
+		let y_labels = axis::percent_labels(100.0);
+		let graph_area = axis::graph_area(area, &x_labels, &y_labels);
+
+		let series: Vec<&[(f64, f64)]> = self
+			.breakdown_percents
+			.iter()
+			.map(|percents| percents.as_slice())
+			.collect();
+		let layers = axis::stacked_area(&series, x_bounds, 100.0, graph_area);
+		let datasets: Vec<Dataset> = layers
+			.iter()
+			.enumerate()
+			.map(|(i, points)| {
+				Dataset::default()
+					.marker(Marker::Braille)
+					.graph_type(GraphType::Scatter)
+					.style(self.breakdown_style(i))
+					.data(points)
+			})
+			.collect();
+
+		Chart::<String, String>::default()
+			.block(block::new(self.colorscheme, &self.title))
+			.x_axis(
+				Axis::default()
+					.bounds(x_bounds)
+					.labels(&x_labels)
+					.style(self.colorscheme.borders)
+					.labels_style(self.colorscheme.text),
+			)
+			.y_axis(
+				Axis::default()
+					.bounds([0.0, 100.0])
+					.labels(&y_labels)
+					.style(self.colorscheme.borders)
+					.labels_style(self.colorscheme.text),
+			)
+			.datasets(&datasets)
+			.render(area, buf);
+
+		let breakdown = &self.breakdown;
+		let bytes = [
+			breakdown.used,
+			breakdown.buffers,
+			breakdown.cache,
+			breakdown.shared,
+			breakdown.slab,
+			breakdown.free,
+		];
+		let mut legend: Vec<(String, Style)> = BREAKDOWN_LABELS
+			.iter()
+			.zip(bytes.iter())
+			.enumerate()
+			.map(|(i, (label, bytes))| {
+				(
+					format!(
+						"{:<9} {:>10} {:3.0}%",
+						label,
+						self.units.format(*bytes),
+						*bytes as f64 / u64::max(breakdown.total, 1) as f64 * 100.0
+					),
+					self.breakdown_style(i),
+				)
+			})
+			.collect();
+		legend.push((
+			format!(
+				"{:<9} {:>10} {:3.0}%",
+				"Available",
+				self.units.format(breakdown.available),
+				breakdown.available as f64 / u64::max(breakdown.total, 1) as f64 * 100.0
+			),
+			self.colorscheme.text,
+		));
+
+		let huge_pages = &self.huge_pages;
+		if huge_pages.total > 0 {
+			legend.push((
+				format!(
+					"{:<9} {} x {}, {} free, {} reserved",
+					"HugePages",
+					huge_pages.total,
+					self.units.format(huge_pages.page_size),
+					huge_pages.free,
+					huge_pages.reserved,
+				),
+				self.colorscheme.text,
+			));
+		}
+		if huge_pages.transparent > 0 {
+			legend.push((
+				format!(
+					"{:<9} {:>10}",
+					"THP",
+					self.units.format(huge_pages.transparent)
+				),
+				self.colorscheme.text,
+			));
+		}
+		for device in &self.zram_devices {
+			legend.push((
+				format!(
+					"{:<9} {}",
+					device.name,
+					compression_text(
+						self.units,
+						device.stored,
+						device.compressed,
+						device.mem_used
+					)
+				),
+				self.colorscheme.text,
+			));
+		}
+		if let Some(zswap) = self.zswap.filter(|zswap| zswap.stored > 0) {
+			legend.push((
+				format!(
+					"{:<9} {}",
+					"zswap",
+					compression_text(self.units, zswap.stored, zswap.compressed, zswap.compressed)
+				),
+				self.colorscheme.text,
+			));
+		}
+
+		for (i, (text, style)) in legend.into_iter().enumerate() {
+			let y = graph_area.y + 1 + i as u16;
+			if y >= graph_area.bottom() {
+				break;
+			}
+			buf.set_stringn(
+				graph_area.x + 1,
+				y,
+				text,
+				graph_area.width.saturating_sub(1) as usize,
+				style,
+			);
+		}
+	}
+
+	// How much memory the swapped out pages really take up when swap is compressed, since the swap
+	// percentage only counts them at their original size.
+	fn compressed_swap_note(&self) -> Option<String> {
+		let mut mem_used = 0;
+		let mut stored = 0;
+		for device in &self.zram_devices {
+			let device_path = format!("/dev/{}", device.name);
+			if self
+				.swap_devices
+				.iter()
+				.any(|swap| swap.name == device_path)
+			{
+				mem_used += device.mem_used;
+				stored += device.stored;
+			}
+		}
+		if let Some(zswap) = self.zswap {
+			mem_used += zswap.compressed;
+			stored += zswap.stored;
+		}
+		let ratio = zram::compression_ratio(stored, mem_used)?;
+		Some(format!(
+			"({} in RAM, {:.1}x)",
+			self.units.format(mem_used),
+			ratio
+		))
+	}
+
+	fn render_paging(&self, area: Rect, buf: &mut Buffer) {
+		block::new(self.colorscheme, &self.title).render(area, buf);
+
+		if area.height < 3 || area.width < 3 {
+			return;
+		}
+
+		let inner = Rect {
+			x: area.x + 1,
+			y: area.y + 1,
+			width: area.width - 2,
+			height: area.height - 2,
+		};
+
+		// Give each sparkline up to 2 rows and leave the rest for the swap table.
+		let rates_height = u16::min(inner.height, PAGING_LABELS.len() as u16 * 2);
+		let row_height = u16::max(rates_height / PAGING_LABELS.len() as u16, 1);
+		for (i, (label, rates)) in PAGING_LABELS.iter().zip(&self.paging_rates).enumerate() {
+			let y = inner.y + i as u16 * row_height;
+			if y >= inner.y + rates_height {
+				break;
+			}
+			buf.set_stringn(
+				inner.x + 1,
+				y,
+				format!(
+					"{:<12} {:>7}/s",
+					label,
+					rates.last().cloned().unwrap_or_default()
+				),
+				PAGING_TEXT_WIDTH as usize,
+				self.colorscheme.text.modifier(Modifier::BOLD),
+			);
+			Sparkline::default()
+				.data(rates.as_slice())
+				.direction(RenderDirection::RTL)
+				.style(self.colorscheme.mem_swap)
+				.render(
+					Rect {
+						x: inner.x + PAGING_TEXT_WIDTH + 1,
+						y,
+						width: inner.width.saturating_sub(PAGING_TEXT_WIDTH + 1),
+						height: row_height,
+					},
+					buf,
+				);
+		}
+
+		let table_area = Rect {
+			x: inner.x,
+			y: inner.y + rates_height,
+			width: inner.width,
+			height: inner.height - rates_height,
+		};
+		if table_area.height < 2 {
+			return;
+		}
+
+		Table::new(
+			["Swap Device", "Type", "Size", "Used", "Prio"].iter(),
+			self.swap_devices.iter().map(|device| {
+				Row::StyledData(
+					vec![
+						device.name.clone(),
+						device.kind.clone(),
+						self.units.format(device.size),
+						self.units.format(device.used),
+						format!("{}", device.priority),
+					]
+					.into_iter(),
+					self.colorscheme.text,
+				)
+			}),
+		)
+		.header_style(self.colorscheme.text.modifier(Modifier::BOLD))
+		.widths(&[
+			// width - (column2 + column3 + column4 + column5 width) - (spaces between columns)
+			Constraint::Length(u16::max(
+				table_area.width.saturating_sub(10 + 10 + 10 + 4 + 6),
+				5,
+			)),
+			Constraint::Length(10),
+			Constraint::Length(10),
+			Constraint::Length(10),
+			Constraint::Length(4),
+		])
+		.column_spacing(1)
+		.header_gap(0)
+		.render(table_area, buf);
+	}
+
+	fn breakdown_style(&self, layer: usize) -> Style {
+		self.colorscheme.cpu_lines[layer % self.colorscheme.cpu_lines.len()]
+	}
+}
+
+impl Widget for &MemWidget<'_> {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		match self.view {
+			MemView::Basic => self.render_basic(area, buf),
+			MemView::Detailed => self.render_detailed(area, buf),
+			MemView::Paging => self.render_paging(area, buf),
+		}
+	}
+}
+
+// e.g. "1.2 GiB stored in 400 MiB (3.0x)"
+fn compression_text(units: Units, stored: u64, compressed: u64, mem_used: u64) -> String {
+	match zram::compression_ratio(stored, compressed) {
+		Some(ratio) => format!(
+			"{} stored in {} ({:.1}x)",
+			units.format(stored),
+			units.format(mem_used),
+			ratio
+		),
+		None => "empty".to_string(),
+	}
+}
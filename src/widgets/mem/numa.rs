@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+
+use super::meminfo::parse_meminfo_fields;
+
+const NODES_DIR: &str = "/sys/devices/system/node";
+
+/// Memory usage of a single NUMA node.
+pub struct NumaNode {
+	pub id: u32,
+	pub total: u64,
+	pub free: u64,
+	/// Memory that isn't free, in the page cache, or reclaimable slab. Nodes don't break out buffers
+	/// like /proc/meminfo does, but they're included in the node's page cache, so this is counted
+	/// the same way as the host's used memory.
+	pub used: u64,
+}
+
+/// Parses the contents of /sys/devices/system/node/node<id>/meminfo, whose lines are the same as
+/// the ones in /proc/meminfo except that they start with `Node <id>`.
+pub fn parse_node_meminfo(contents: &str) -> HashMap<String, u64> {
+	contents
+		.lines()
+		.filter_map(|line| parse_meminfo_fields(line.split_whitespace().skip(2)))
+		.collect()
+}
+
+impl NumaNode {
+	pub fn from_meminfo(id: u32, meminfo: &HashMap<String, u64>) -> NumaNode {
+		let field = |key: &str| meminfo.get(key).cloned().unwrap_or_default();
+
+		let total = field("MemTotal");
+		let free = field("MemFree");
+		NumaNode {
+			id,
+			total,
+			free,
+			used: total.saturating_sub(free + field("FilePages") + field("SReclaimable")),
+		}
+	}
+}
+
+/// Returns the nodes that have memory, ordered by id.
+pub fn read_numa_nodes() -> io::Result<Vec<NumaNode>> {
+	let mut nodes = Vec::new();
+	for entry in fs::read_dir(NODES_DIR)? {
+		let entry = entry?;
+		let id = match entry
+			.file_name()
+			.to_str()
+			.filter(|name| name.starts_with("node"))
+			.and_then(|name| name["node".len()..].parse().ok())
+		{
+			Some(id) => id,
+			None => continue,
+		};
+		let meminfo = parse_node_meminfo(&fs::read_to_string(entry.path().join("meminfo"))?);
+		let node = NumaNode::from_meminfo(id, &meminfo);
+		// CPU-only nodes don't have any memory to show.
+		if node.total > 0 {
+			nodes.push(node);
+		}
+	}
+	nodes.sort_by_key(|node| node.id);
+	Ok(nodes)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn it_parses_node_meminfo() {
+		let meminfo = parse_node_meminfo(
+			"Node 1 MemTotal:       16000 kB
+Node 1 MemFree:         4000 kB
+Node 1 FilePages:       2000 kB
+Node 1 SReclaimable:     500 kB
+Node 1 HugePages_Total:     0
+",
+		);
+		let node = NumaNode::from_meminfo(1, &meminfo);
+		assert_eq!(node.total, 16000 * 1024);
+		assert_eq!(node.free, 4000 * 1024);
+		assert_eq!(node.used, 9500 * 1024);
+		assert_eq!(meminfo.get("HugePages_Total"), Some(&0));
+	}
+}
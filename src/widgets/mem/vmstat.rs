@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+
+/// Parses the contents of /proc/vmstat into a map of counter names to values.
+pub fn parse_vmstat(contents: &str) -> HashMap<String, u64> {
+	contents
+		.lines()
+		.filter_map(|line| {
+			let mut fields = line.split_whitespace();
+			let key = fields.next()?;
+			let value = fields.next()?.parse().ok()?;
+			Some((key.to_string(), value))
+		})
+		.collect()
+}
+
+pub fn read_vmstat() -> io::Result<HashMap<String, u64>> {
+	Ok(parse_vmstat(&fs::read_to_string("/proc/vmstat")?))
+}
+
+pub struct SwapDevice {
+	pub name: String,
+	pub kind: String,
+	pub size: u64,
+	pub used: u64,
+	pub priority: i64,
+}
+
+/// Parses the contents of /proc/swaps, e.g.:
+/// Filename                                Type            Size    Used    Priority
+/// /dev/zram0                              partition       4194300 0       100
+pub fn parse_swaps(contents: &str) -> Vec<SwapDevice> {
+	contents
+		.lines()
+		.skip(1)
+		.filter_map(|line| {
+			let fields: Vec<&str> = line.split_whitespace().collect();
+			if fields.len() < 5 {
+				return None;
+			}
+			Some(SwapDevice {
+				// Spaces in paths are escaped as \040.
+				name: fields[0].replace("\\040", " "),
+				kind: fields[1].to_string(),
+				size: fields[2].parse::<u64>().ok()? * 1024,
+				used: fields[3].parse::<u64>().ok()? * 1024,
+				priority: fields[4].parse().ok()?,
+			})
+		})
+		.collect()
+}
+
+pub fn read_swaps() -> io::Result<Vec<SwapDevice>> {
+	Ok(parse_swaps(&fs::read_to_string("/proc/swaps")?))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn it_parses_vmstat() {
+		let vmstat = parse_vmstat("pswpin 12\npswpout 34\npgmajfault 5\n");
+		assert_eq!(vmstat.get("pswpout"), Some(&34));
+		assert_eq!(vmstat.get("pgfault"), None);
+	}
+
+	#[test]
+	fn it_parses_swaps() {
+		let swaps = parse_swaps(
+			"Filename\t\t\t\tType\t\tSize\tUsed\tPriority
+/dev/zram0                              partition\t4194300\t1024\t100
+/swap\\040file                           file\t\t1048572\t0\t-2
+",
+		);
+		assert_eq!(swaps.len(), 2);
+		assert_eq!(swaps[0].name, "/dev/zram0");
+		assert_eq!(swaps[0].size, 4_194_300 * 1024);
+		assert_eq!(swaps[0].used, 1024 * 1024);
+		assert_eq!(swaps[1].name, "/swap file");
+		assert_eq!(swaps[1].kind, "file");
+		assert_eq!(swaps[1].priority, -2);
+	}
+}
@@ -0,0 +1,91 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const BLOCK_DIR: &str = "/sys/block";
+
+/// A compressed RAM block device, which is usually used as swap.
+pub struct ZramDevice {
+	pub name: String,
+	/// Size of the data before compression.
+	pub stored: u64,
+	pub compressed: u64,
+	/// Memory used by the device including allocator overhead, which is its real footprint.
+	pub mem_used: u64,
+}
+
+/// Parses the contents of /sys/block/<name>/mm_stat, whose first fields are the original data
+/// size, the compressed data size, and the total memory used, all in bytes.
+pub fn parse_mm_stat(name: &str, contents: &str) -> Option<ZramDevice> {
+	let mut fields = contents.split_whitespace().map(|field| field.parse().ok());
+	Some(ZramDevice {
+		name: name.to_string(),
+		stored: fields.next()??,
+		compressed: fields.next()??,
+		mem_used: fields.next()??,
+	})
+}
+
+fn read_zram_device(name: &str, path: &Path) -> io::Result<Option<ZramDevice>> {
+	// Devices that haven't been given a size aren't in use.
+	let disksize: u64 = fs::read_to_string(path.join("disksize"))?
+		.trim()
+		.parse()
+		.unwrap_or_default();
+	if disksize == 0 {
+		return Ok(None);
+	}
+	Ok(parse_mm_stat(
+		name,
+		&fs::read_to_string(path.join("mm_stat"))?,
+	))
+}
+
+pub fn read_zram_devices() -> io::Result<Vec<ZramDevice>> {
+	let mut devices = Vec::new();
+	for entry in fs::read_dir(BLOCK_DIR)? {
+		let entry = entry?;
+		let name = entry.file_name().to_string_lossy().into_owned();
+		if !name.starts_with("zram") {
+			continue;
+		}
+		if let Some(device) = read_zram_device(&name, &entry.path())? {
+			devices.push(device);
+		}
+	}
+	devices.sort_by(|a, b| a.name.cmp(&b.name));
+	Ok(devices)
+}
+
+/// How many times smaller the data got, or `None` if there's nothing stored.
+pub fn compression_ratio(stored: u64, compressed: u64) -> Option<f64> {
+	if compressed == 0 {
+		None
+	} else {
+		Some(stored as f64 / compressed as f64)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn it_parses_mm_stat() {
+		// orig_data_size compr_data_size mem_used_total mem_limit mem_used_max same_pages
+		// pages_compacted huge_pages
+		let device = parse_mm_stat(
+			"zram0",
+			"  4096000   1024000   1200000        0   1300000       12        0        3\n",
+		)
+		.unwrap();
+		assert_eq!(device.stored, 4_096_000);
+		assert_eq!(device.compressed, 1_024_000);
+		assert_eq!(device.mem_used, 1_200_000);
+		assert_eq!(
+			compression_ratio(device.stored, device.compressed),
+			Some(4.0)
+		);
+		assert!(parse_mm_stat("zram0", "").is_none());
+	}
+}
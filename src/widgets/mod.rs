@@ -1,20 +1,41 @@
+mod axis;
 mod battery;
 mod block;
+mod connections;
 mod cpu;
 mod disk;
 mod help_menu;
 mod mem;
 mod net;
 mod proc;
+mod protocols;
+mod psi;
+mod raid;
 mod statusbar;
 mod temp;
 
 pub use self::battery::BatteryWidget;
+pub use self::connections::ConnectionsWidget;
 pub use self::cpu::CpuWidget;
 pub use self::disk::DiskWidget;
 pub use self::help_menu::HelpMenu;
 pub use self::mem::MemWidget;
-pub use self::net::NetWidget;
+pub use self::net::{NetWidget, Quota, UsageTracker};
 pub use self::proc::ProcWidget;
+pub use self::protocols::ProtocolsWidget;
+pub use self::psi::PsiWidget;
+pub use self::raid::RaidWidget;
 pub use self::statusbar::Statusbar;
 pub use self::temp::TempWidget;
+
+/// A table whose selected row is moved with the process navigation keys.
+pub trait ScrollableTable {
+	fn scroll_up(&mut self);
+	fn scroll_down(&mut self);
+	fn scroll_top(&mut self);
+	fn scroll_bottom(&mut self);
+	fn scroll_half_page_down(&mut self);
+	fn scroll_half_page_up(&mut self);
+	fn scroll_full_page_down(&mut self);
+	fn scroll_full_page_up(&mut self);
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::fs;
+use std::net::IpAddr;
+use std::path::Path;
+
+use nix::ifaddrs::getifaddrs;
+use nix::sys::socket::SockAddr;
+
+const CLASS_DIR: &str = "/sys/class/net";
+const PROC_NET_DEV: &str = "/proc/net/dev";
+const PROC_NET_WIRELESS: &str = "/proc/net/wireless";
+
+/// Signal readings of a wireless interface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wireless {
+	/// The driver's own measure of link quality, which usually goes up to 70.
+	pub link: f64,
+	/// Signal level in dBm.
+	pub level: f64,
+	/// Noise level in dBm, which a lot of drivers don't report.
+	pub noise: Option<f64>,
+}
+
+/// Link details of a network interface.
+#[derive(Default, Clone)]
+pub struct InterfaceInfo {
+	/// e.g. `up`, `down`, or `unknown` for interfaces like loopback that don't report it.
+	pub state: String,
+	pub mtu: Option<u32>,
+	/// Unknown for virtual interfaces and links that are down.
+	pub speed_mbps: Option<u64>,
+	pub addresses: Vec<IpAddr>,
+	pub wireless: Option<Wireless>,
+}
+
+fn read_value<T: std::str::FromStr>(path: &Path) -> Option<T> {
+	fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Reads the link details of every interface from /sys/class/net and their addresses.
+pub fn read_interfaces() -> HashMap<String, InterfaceInfo> {
+	let mut interfaces: HashMap<String, InterfaceInfo> = HashMap::new();
+
+	if let Ok(entries) = fs::read_dir(CLASS_DIR) {
+		for entry in entries.filter_map(|entry| entry.ok()) {
+			let path = entry.path();
+			interfaces.insert(
+				entry.file_name().to_string_lossy().into_owned(),
+				InterfaceInfo {
+					state: read_value(&path.join("operstate")).unwrap_or_default(),
+					mtu: read_value(&path.join("mtu")),
+					// The speed is -1 or can't be read when it's unknown.
+					speed_mbps: read_value::<i64>(&path.join("speed"))
+						.filter(|speed| *speed > 0)
+						.map(|speed| speed as u64),
+					addresses: Vec::new(),
+					wireless: None,
+				},
+			);
+		}
+	}
+
+	if let Ok(addresses) = getifaddrs() {
+		for address in addresses {
+			if let Some(SockAddr::Inet(inet)) = address.address {
+				interfaces
+					.entry(address.interface_name)
+					.or_default()
+					.addresses
+					.push(inet.to_std().ip());
+			}
+		}
+	}
+
+	for (name, wireless) in read_wireless() {
+		interfaces.entry(name).or_default().wireless = Some(wireless);
+	}
+
+	interfaces
+}
+
+fn read_wireless() -> HashMap<String, Wireless> {
+	fs::read_to_string(PROC_NET_WIRELESS)
+		.map(|contents| parse_wireless(&contents))
+		.unwrap_or_default()
+}
+
+// e.g. " wlan0: 0000   54.  -56.  -256        0      0      0      0     36        0"
+// The values end in a '.' when they've been updated since they were last read.
+fn parse_wireless(contents: &str) -> HashMap<String, Wireless> {
+	contents
+		.lines()
+		.skip(2)
+		.filter_map(|line| {
+			let mut parts = line.splitn(2, ':');
+			let name = parts.next()?.trim();
+			let fields: Vec<f64> = parts
+				.next()?
+				.split_whitespace()
+				.skip(1)
+				.take(3)
+				.filter_map(|field| field.trim_end_matches('.').parse().ok())
+				.collect();
+			if fields.len() < 3 {
+				return None;
+			}
+			Some((
+				name.to_string(),
+				Wireless {
+					link: fields[0],
+					level: fields[1],
+					// -256 dBm is what drivers report when they don't know.
+					noise: Some(fields[2]).filter(|noise| *noise > -256.0),
+				},
+			))
+		})
+		.collect()
+}
+
+/// Reads how many packets each interface has dropped due to FIFO buffer overruns as (receive,
+/// transmit), which psutil leaves out of its counters.
+pub fn read_fifo_overruns() -> HashMap<String, (u64, u64)> {
+	fs::read_to_string(PROC_NET_DEV)
+		.map(|contents| parse_fifo_overruns(&contents))
+		.unwrap_or_default()
+}
+
+// e.g. "  eth0: 1468 12 0 0 3 0 0 0 2196 20 0 0 1 0 0 0"
+fn parse_fifo_overruns(contents: &str) -> HashMap<String, (u64, u64)> {
+	contents
+		.lines()
+		.skip(2)
+		.filter_map(|line| {
+			let mut parts = line.splitn(2, ':');
+			let name = parts.next()?.trim();
+			let fields: Vec<&str> = parts.next()?.split_whitespace().collect();
+			let recv = fields.get(4)?.parse().ok()?;
+			let sent = fields.get(12)?.parse().ok()?;
+			Some((name.to_string(), (recv, sent)))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn it_parses_fifo_overruns() {
+		let overruns = parse_fifo_overruns(
+			"Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:   49525    5057    0    0    0     0          0         0    49525    5057    0    0    0     0       0          0
+  eth0:1468203   12001    2    1    3     0          0         0  2196000   20000    0    0    1     0       0          0
+",
+		);
+		assert_eq!(overruns.len(), 2);
+		assert_eq!(overruns["lo"], (0, 0));
+		assert_eq!(overruns["eth0"], (3, 1));
+	}
+
+	#[test]
+	fn it_parses_wireless() {
+		let wireless = parse_wireless(
+			"Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+ face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+ wlan0: 0000   54.  -56.  -256        0      0      0      0     36        0
+  wlp2s0: 0000   31   -79   -95.       0      0      0      2      0        0
+",
+		);
+		assert_eq!(
+			wireless["wlan0"],
+			Wireless {
+				link: 54.0,
+				level: -56.0,
+				noise: None,
+			}
+		);
+		assert_eq!(wireless["wlp2s0"].noise, Some(-95.0));
+	}
+}
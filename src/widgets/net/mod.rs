@@ -0,0 +1,904 @@
+mod interfaces;
+mod usage;
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::prelude::*;
+use glob::Pattern;
+use num_rational::Ratio;
+use psutil::network;
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Rect};
+use tui::style::Modifier;
+// use tui::widgets::{RenderDirection, Sparkline, Widget};
+use crate::sparkline::{RenderDirection, Sparkline};
+use tui::widgets::{Row, Table, Widget};
+
+use crate::colorscheme::Colorscheme;
+use crate::history::{History, Stats};
+use crate::units::Units;
+use crate::update::UpdatableWidget;
+use crate::widgets::block;
+
+use self::interfaces::{InterfaceInfo, Wireless};
+use self::usage::Usage;
+pub use self::usage::{Quota, UsageTracker};
+
+const ALL_INTERFACES: &str = "all";
+
+// VPN interfaces that are left out of the 'all' total unless they match `--interface-include`.
+const EXCLUDED_INTERFACES: [&str; 1] = ["tun0"];
+
+// Widths of the table columns after the interface name, which are dropped from the right when
+// there isn't enough room. The addresses get whatever is left.
+const TABLE_COLUMN_WIDTHS: [u16; 9] = [10, 10, 9, 9, 6, 7, 5, 6, 12];
+
+// The usual top of the link quality in /proc/net/wireless, which the sparklines are scaled to.
+const WIRELESS_LINK_MAX: u64 = 70;
+
+// Number of updates between saves of the data usage, which is also saved on exit.
+const USAGE_SAVE_INTERVAL: u64 = 60;
+
+// Loopback traffic never leaves the machine, so it doesn't count toward data usage.
+const LOOPBACK_INTERFACE: &str = "lo";
+
+const MEAN_LINE_SYMBOL: &str = "╌";
+
+// Multiplier that keeps some precision when log scaled values are rounded for the sparkline.
+const LOG_SCALE_PRECISION: f64 = 1000.0;
+
+#[derive(PartialEq, Clone, Copy)]
+enum NetScale {
+	// Against the largest value on screen.
+	Visible,
+	// Logarithmic against the largest value on screen, so that small traffic is still visible.
+	Log,
+	// Against the speed of the links.
+	Link,
+}
+
+#[derive(PartialEq)]
+enum NetView {
+	Sparklines,
+	Table,
+}
+
+#[derive(Default)]
+struct Interface {
+	total_bytes_recv: u64,
+	total_bytes_sent: u64,
+	// `None` until we have two readings of the interface's counters.
+	bytes_recv: Option<u64>,
+	bytes_sent: Option<u64>,
+	// Errors, drops, and FIFO overruns in both directions.
+	total_errors: u64,
+	errors_increased: bool,
+	info: InterfaceInfo,
+}
+
+/// Packet counters for one direction of traffic.
+#[derive(Default, Clone, Copy)]
+struct PacketCounters {
+	packets: u64,
+	errors: u64,
+	drops: u64,
+	fifo: u64,
+}
+
+impl PacketCounters {
+	fn add(&mut self, other: PacketCounters) {
+		self.packets += other.packets;
+		self.errors += other.errors;
+		self.drops += other.drops;
+		self.fifo += other.fifo;
+	}
+
+	fn problems(&self) -> u64 {
+		self.errors + self.drops + self.fifo
+	}
+}
+
+/// One direction of traffic, as drawn in each half of the widget.
+struct Traffic<'a> {
+	label: &'a str,
+	total_bytes: u64,
+	bytes: &'a History<u64>,
+	packets: PacketCounters,
+	previous_packets: Option<PacketCounters>,
+}
+
+pub struct NetWidget<'a> {
+	title: String,
+	update_interval: Ratio<u64>,
+	colorscheme: &'a Colorscheme,
+	units: Units,
+
+	// The interface shown in the sparklines, or `ALL_INTERFACES`.
+	interface: String,
+	view: NetView,
+	scale: NetScale,
+
+	// Which interfaces are summed when showing all of them.
+	interface_include: Vec<Pattern>,
+	interface_exclude: Vec<Pattern>,
+
+	bytes_recv: History<u64>,
+	bytes_sent: History<u64>,
+
+	total_bytes_recv: u64,
+	total_bytes_sent: u64,
+
+	// Summed over the same interfaces as the bytes, along with the counters from the previous
+	// update to get rates from.
+	packets_recv: PacketCounters,
+	packets_sent: PacketCounters,
+	previous_packets: Option<(PacketCounters, PacketCounters)>,
+
+	interfaces: BTreeMap<String, Interface>,
+	// Link quality of each wireless interface.
+	wireless_history: HashMap<String, History<u64>>,
+
+	// Daily and monthly data usage when it's being tracked.
+	usage: Option<UsageTracker>,
+	updates_since_save: u64,
+
+	show_stats: bool,
+	show_mean_lines: bool,
+
+	collector: network::NetIoCountersCollector,
+}
+
+// The link quality followed by the signal and noise levels in dBm, e.g. "54 -56/-95".
+fn format_signal(wireless: &Wireless) -> String {
+	let signal = format!("{:.0} {:.0}", wireless.link, wireless.level);
+	match wireless.noise {
+		Some(noise) => format!("{}/{:.0}", signal, noise),
+		None => signal,
+	}
+}
+
+fn title(interface: &str) -> String {
+	if interface == ALL_INTERFACES {
+		" Network Usage ".to_string()
+	} else {
+		format!(" Network Usage: {} ", interface)
+	}
+}
+
+impl NetWidget<'_> {
+	pub fn new<'a>(
+		colorscheme: &'a Colorscheme,
+		units: Units,
+		interface: &str,
+		interface_include: Vec<Pattern>,
+		interface_exclude: Vec<Pattern>,
+		usage: Option<UsageTracker>,
+	) -> NetWidget<'a> {
+		NetWidget {
+			title: title(interface),
+			update_interval: Ratio::from_integer(1),
+			colorscheme,
+			units,
+
+			interface: interface.to_string(),
+			view: NetView::Sparklines,
+			scale: NetScale::Visible,
+
+			interface_include,
+			interface_exclude,
+
+			bytes_recv: History::default(),
+			bytes_sent: History::default(),
+
+			total_bytes_recv: 0,
+			total_bytes_sent: 0,
+
+			packets_recv: PacketCounters::default(),
+			packets_sent: PacketCounters::default(),
+			previous_packets: None,
+
+			interfaces: BTreeMap::new(),
+			wireless_history: HashMap::new(),
+
+			usage,
+			updates_since_save: 0,
+
+			show_stats: false,
+			show_mean_lines: false,
+
+			collector: network::NetIoCountersCollector::default(),
+		}
+	}
+}
+
+impl NetWidget<'_> {
+	// Virtual interfaces like VPNs and container bridges are excluded from the combined total since
+	// their traffic gets double counted along with the hardware interfaces they are operating on.
+	fn is_interface_counted(&self, name: &str) -> bool {
+		if self.interface != ALL_INTERFACES {
+			return name == self.interface;
+		}
+		let is_included = self
+			.interface_include
+			.iter()
+			.any(|pattern| pattern.matches(name));
+		if !is_included
+			&& (!self.interface_include.is_empty() || EXCLUDED_INTERFACES.contains(&name))
+		{
+			return false;
+		}
+		!self
+			.interface_exclude
+			.iter()
+			.any(|pattern| pattern.matches(name))
+	}
+
+	pub fn toggle_view(&mut self) {
+		self.view = match self.view {
+			NetView::Sparklines => NetView::Table,
+			NetView::Table => NetView::Sparklines,
+		};
+	}
+
+	pub fn cycle_scale(&mut self) {
+		self.scale = match self.scale {
+			NetScale::Visible => NetScale::Log,
+			NetScale::Log => NetScale::Link,
+			NetScale::Link => NetScale::Visible,
+		};
+	}
+
+	/// The combined speed of the links being graphed in bytes per second, which is unknown for
+	/// virtual interfaces.
+	fn link_speed(&self) -> Option<u64> {
+		let speeds: Vec<u64> = self
+			.interfaces
+			.iter()
+			.filter(|(name, _interface)| self.is_interface_counted(name))
+			.filter_map(|(_name, interface)| interface.info.speed_mbps)
+			.collect();
+		if speeds.is_empty() {
+			None
+		} else {
+			Some(speeds.iter().sum::<u64>() * 1_000_000 / 8)
+		}
+	}
+
+	/// Switches the sparklines to the next interface, going through all of them combined first.
+	pub fn cycle_interface(&mut self) {
+		let names: Vec<&str> = std::iter::once(ALL_INTERFACES)
+			.chain(self.interfaces.keys().map(|name| name.as_str()))
+			.collect();
+		let next = match names.iter().position(|name| *name == self.interface) {
+			Some(index) => names[(index + 1) % names.len()],
+			None => ALL_INTERFACES,
+		};
+		self.interface = next.to_string();
+		self.title = title(next);
+
+		// Start the sparklines over since the old data was from another interface.
+		self.bytes_recv = History::default();
+		self.bytes_sent = History::default();
+		self.bytes_recv.push(0);
+		self.bytes_sent.push(0);
+		self.total_bytes_recv = 0;
+		self.total_bytes_sent = 0;
+		self.previous_packets = None;
+	}
+
+	/// Saves the data usage if it's being tracked.
+	pub fn save_usage(&mut self) {
+		if let Some(usage) = self.usage.as_mut() {
+			// Nothing is lost if this fails since the totals are kept in memory and saved again later.
+			if let Err(err) = usage.save() {
+				log::error!("failed to save the data usage: {}", err);
+			}
+		}
+	}
+
+	fn record_usage(&mut self) {
+		let day = Local::now().format("%Y-%m-%d").to_string();
+		// Saving adds everything since the last save to the day that was last recorded, so the
+		// traffic from before midnight is saved before any of the new day's is recorded.
+		if let Some(true) = self.usage.as_ref().map(|usage| usage.is_new_day(&day)) {
+			self.updates_since_save = 0;
+			self.save_usage();
+		}
+
+		let usage = match self.usage.as_mut() {
+			Some(usage) => usage,
+			None => return,
+		};
+		let counters = self
+			.interfaces
+			.iter()
+			.filter(|(name, _interface)| *name != LOOPBACK_INTERFACE)
+			.map(|(name, interface)| {
+				(
+					name.clone(),
+					Usage {
+						recv: interface.total_bytes_recv,
+						sent: interface.total_bytes_sent,
+					},
+				)
+			})
+			.collect();
+		usage.record(counters, &usage::read_boot_id().unwrap_or_default(), &day);
+
+		self.updates_since_save += 1;
+		if self.updates_since_save >= USAGE_SAVE_INTERVAL {
+			self.updates_since_save = 0;
+			self.save_usage();
+		}
+	}
+
+	// e.g. "Today 1.20 GiB  Month 12.3 GiB / 50.0 GiB (24%)", right aligned in the top border.
+	fn render_usage(&self, area: Rect, buf: &mut Buffer) {
+		let usage = match self.usage.as_ref() {
+			Some(usage) => usage,
+			None => return,
+		};
+		let now = Local::now();
+		let is_counted = |name: &str| self.is_interface_counted(name);
+		let today = usage.day_usage(&now.format("%Y-%m-%d").to_string(), is_counted);
+		let month = usage.month_usage(&now.format("%Y-%m").to_string(), is_counted);
+
+		let mut month_text = format!("Month {}", self.units.format(month.total()));
+		let mut style = self.colorscheme.titles;
+		if let Some(quota) = usage.quota {
+			let percent = month.total() as f64 / quota.bytes as f64 * 100.0;
+			month_text = format!(
+				"{} / {} ({:.0}%)",
+				month_text,
+				self.units.format(quota.bytes),
+				percent
+			);
+			if percent >= quota.warning_percent {
+				style = self.colorscheme.temp_high;
+			}
+		}
+
+		// Today's usage is left out first when there isn't room next to the title.
+		let title_width = self.title.chars().count() as u16;
+		let texts = [
+			format!(
+				" Today {}  {} ",
+				self.units.format(today.total()),
+				month_text
+			),
+			format!(" {} ", month_text),
+		];
+		for text in texts.iter() {
+			let text_width = text.chars().count() as u16;
+			if area.width >= title_width + text_width + 4 {
+				buf.set_string(area.right() - 1 - text_width, area.y, text, style);
+				break;
+			}
+		}
+	}
+
+	pub fn toggle_stats(&mut self) {
+		self.show_stats = !self.show_stats;
+	}
+
+	pub fn toggle_mean_lines(&mut self) {
+		self.show_mean_lines = !self.show_mean_lines;
+	}
+
+	fn render_traffic(&self, area: Rect, buf: &mut Buffer, traffic: Traffic) {
+		let Traffic {
+			label,
+			total_bytes,
+			bytes,
+			packets,
+			previous_packets,
+		} = traffic;
+		let header_height = if self.show_stats { 5 } else { 4 };
+		let sparkline_area = Rect {
+			x: area.x,
+			y: area.y + header_height,
+			width: area.width,
+			height: area.height.saturating_sub(header_height),
+		};
+
+		let data = bytes.as_slice();
+		let visible_data = &data[data.len().saturating_sub(sparkline_area.width as usize)..];
+		let stats = Stats::new(visible_data.iter().map(|bytes| *bytes as f64));
+		let visible_max = visible_data.iter().max().cloned().unwrap_or_default();
+
+		let link_speed = self.link_speed();
+		let (max, scale_text) = match (self.scale, link_speed) {
+			(NetScale::Link, Some(link_speed)) => (
+				link_speed,
+				format!("link {}/s", self.units.format(link_speed)),
+			),
+			(NetScale::Link, None) => (
+				visible_max,
+				format!("max {}/s, no link speed", self.units.format(visible_max)),
+			),
+			(NetScale::Log, _) => (
+				visible_max,
+				format!("log, max {}/s", self.units.format(visible_max)),
+			),
+			(NetScale::Visible, _) => (
+				visible_max,
+				format!("max {}/s", self.units.format(visible_max)),
+			),
+		};
+		let scale = |value: u64| {
+			if self.scale == NetScale::Log {
+				((value as f64 + 1.0).ln() * LOG_SCALE_PRECISION) as u64
+			} else {
+				value
+			}
+		};
+		let log_data: Vec<u64>;
+		let data = if self.scale == NetScale::Log {
+			log_data = data.iter().map(|value| scale(*value)).collect();
+			&log_data
+		} else {
+			data
+		};
+		let max = scale(max);
+
+		let total_text = format!("Total {}: {}", label, self.units.format(total_bytes));
+		let total_width = total_text.chars().count() as u16;
+		buf.set_string(
+			area.x + 1,
+			area.y + 1,
+			total_text,
+			self.colorscheme.text.modifier(Modifier::BOLD),
+		);
+
+		// Right aligned when there's room for it next to the total.
+		let scale_width = scale_text.chars().count() as u16;
+		if area.width >= total_width + scale_width + 4 {
+			buf.set_string(
+				area.right() - 1 - scale_width,
+				area.y + 1,
+				scale_text,
+				self.colorscheme.text,
+			);
+		}
+
+		buf.set_string(
+			area.x + 1,
+			area.y + 2,
+			format!(
+				"{}/s:     {}/s",
+				label,
+				self.units.format(bytes.last().unwrap().to_owned())
+			),
+			self.colorscheme.text.modifier(Modifier::BOLD),
+		);
+
+		// Highlight the counters while there are new errors so a flaky link stands out.
+		let (packets_per_second, style) = match previous_packets {
+			Some(previous) => (
+				packets.packets.saturating_sub(previous.packets).to_string(),
+				if packets.problems() > previous.problems() {
+					self.colorscheme.temp_high
+				} else {
+					self.colorscheme.text
+				},
+			),
+			None => ("0".to_string(), self.colorscheme.text),
+		};
+		// Skip the lines that don't fit when the widget is short.
+		if area.height > 3 {
+			buf.set_stringn(
+				area.x + 1,
+				area.y + 3,
+				format!(
+					"Pkts/s:   {}  Errs: {}  Drops: {}  FIFO: {}",
+					packets_per_second, packets.errors, packets.drops, packets.fifo
+				),
+				area.width.saturating_sub(2) as usize,
+				style,
+			);
+		}
+
+		if let (true, true, Some(stats)) = (self.show_stats, area.height > 4, &stats) {
+			buf.set_stringn(
+				area.x + 1,
+				area.y + 4,
+				format!(
+					"min {}/s max {}/s avg {}/s p95 {}/s",
+					self.units.format(stats.min as u64),
+					self.units.format(stats.max as u64),
+					self.units.format(stats.mean as u64),
+					self.units.format(stats.p95 as u64),
+				),
+				area.width.saturating_sub(2) as usize,
+				self.colorscheme.text,
+			);
+		}
+
+		Sparkline::default()
+			.data(data)
+			.direction(RenderDirection::RTL)
+			.show_baseline(true)
+			.max(max)
+			.style(self.colorscheme.net_bars)
+			.render(sparkline_area, buf);
+
+		if let (true, Some(stats)) = (self.show_mean_lines, &stats) {
+			if max > 0 && sparkline_area.height > 0 {
+				// Same scaling as the sparkline, which divides each row into 8 levels.
+				let rows =
+					(scale(stats.mean as u64) * u64::from(sparkline_area.height) / max) as u16;
+				let y = sparkline_area.bottom() - 1 - u16::min(rows, sparkline_area.height - 1);
+				for x in (sparkline_area.left()..sparkline_area.right()).step_by(2) {
+					let cell = buf.get_mut(x, y);
+					if cell.symbol == " " {
+						cell.set_symbol(MEAN_LINE_SYMBOL)
+							.set_style(self.colorscheme.net_bars);
+					}
+				}
+			}
+		}
+	}
+
+	// e.g. "wlan0 link 54 signal -56 dBm noise -95 dBm ▅▆▆▃▂▅"
+	fn render_wireless(&self, area: Rect, buf: &mut Buffer, name: &str, wireless: &Wireless) {
+		let mut text = format!(
+			"{} link {:.0} signal {:.0} dBm",
+			name, wireless.link, wireless.level
+		);
+		if let Some(noise) = wireless.noise {
+			text = format!("{} noise {:.0} dBm", text, noise);
+		}
+		let text_width = text.chars().count() as u16 + 1;
+		buf.set_stringn(
+			area.x,
+			area.y,
+			text,
+			area.width as usize,
+			self.colorscheme.text,
+		);
+
+		if let Some(history) = self.wireless_history.get(name) {
+			if area.width > text_width {
+				let data = history.as_slice();
+				let sparkline_area = Rect {
+					x: area.x + text_width,
+					y: area.y,
+					width: area.width - text_width,
+					height: 1,
+				};
+				let visible_data =
+					&data[data.len().saturating_sub(sparkline_area.width as usize)..];
+				Sparkline::default()
+					.data(data)
+					.direction(RenderDirection::RTL)
+					.max(u64::max(
+						WIRELESS_LINK_MAX,
+						visible_data.iter().max().cloned().unwrap_or_default(),
+					))
+					.style(self.colorscheme.net_bars)
+					.render(sparkline_area, buf);
+			}
+		}
+	}
+}
+
+impl UpdatableWidget for NetWidget<'_> {
+	fn update(&mut self) {
+		let io_counters_pernic = self.collector.net_io_counters_pernic().unwrap();
+
+		let mut infos = interfaces::read_interfaces();
+		let fifo_overruns = interfaces::read_fifo_overruns();
+		let packet_counters = |name: &str, counters: &network::NetIoCounters| {
+			let (fifo_recv, fifo_sent) = fifo_overruns.get(name).cloned().unwrap_or_default();
+			(
+				PacketCounters {
+					packets: counters.packets_recv(),
+					errors: counters.err_in(),
+					drops: counters.drop_in(),
+					fifo: fifo_recv,
+				},
+				PacketCounters {
+					packets: counters.packets_sent(),
+					errors: counters.err_out(),
+					drops: counters.drop_out(),
+					fifo: fifo_sent,
+				},
+			)
+		};
+
+		let previous_interfaces = &self.interfaces;
+		self.interfaces = io_counters_pernic
+			.iter()
+			.map(|(name, counters)| {
+				let previous = previous_interfaces.get(name);
+				let (recv, sent) = packet_counters(name, counters);
+				let total_errors = recv.problems() + sent.problems();
+				(
+					name.clone(),
+					Interface {
+						total_bytes_recv: counters.bytes_recv(),
+						total_bytes_sent: counters.bytes_sent(),
+						bytes_recv: previous.map(|previous| {
+							counters
+								.bytes_recv()
+								.saturating_sub(previous.total_bytes_recv)
+						}),
+						bytes_sent: previous.map(|previous| {
+							counters
+								.bytes_sent()
+								.saturating_sub(previous.total_bytes_sent)
+						}),
+						total_errors,
+						errors_increased: previous
+							.map(|previous| total_errors > previous.total_errors)
+							.unwrap_or_default(),
+						info: infos.remove(name).unwrap_or_default(),
+					},
+				)
+			})
+			.collect();
+
+		let interfaces = &self.interfaces;
+		self.wireless_history.retain(|name, _history| {
+			interfaces
+				.get(name)
+				.map(|interface| interface.info.wireless.is_some())
+				.unwrap_or_default()
+		});
+		for (name, interface) in interfaces.iter() {
+			if let Some(wireless) = &interface.info.wireless {
+				self.wireless_history
+					.entry(name.clone())
+					.or_default()
+					.push(wireless.link.max(0.0) as u64);
+			}
+		}
+
+		let mut packets_recv = PacketCounters::default();
+		let mut packets_sent = PacketCounters::default();
+		let io_counters: network::NetIoCounters = io_counters_pernic
+			.into_iter()
+			.filter(|(name, _counters)| self.is_interface_counted(name))
+			.map(|(name, counters)| {
+				let (recv, sent) = packet_counters(&name, &counters);
+				packets_recv.add(recv);
+				packets_sent.add(sent);
+				counters
+			})
+			.sum();
+
+		if self.total_bytes_recv == 0 {
+			self.bytes_recv.push(0);
+			self.bytes_sent.push(0);
+		} else {
+			self.bytes_recv.push(
+				io_counters
+					.bytes_recv()
+					.saturating_sub(self.total_bytes_recv),
+			);
+			self.bytes_sent.push(
+				io_counters
+					.bytes_sent()
+					.saturating_sub(self.total_bytes_sent),
+			);
+		}
+
+		self.previous_packets = if self.total_bytes_recv == 0 {
+			None
+		} else {
+			Some((self.packets_recv, self.packets_sent))
+		};
+		self.packets_recv = packets_recv;
+		self.packets_sent = packets_sent;
+
+		self.total_bytes_recv = io_counters.bytes_recv();
+		self.total_bytes_sent = io_counters.bytes_sent();
+
+		self.record_usage();
+	}
+
+	fn get_update_interval(&self) -> Ratio<u64> {
+		self.update_interval
+	}
+}
+
+impl NetWidget<'_> {
+	fn render_table(&self, area: Rect, buf: &mut Buffer) {
+		// width - (border width) - (interface name width) - (space after the name)
+		let mut width = area.width.saturating_sub(2 + 10 + 1);
+		let mut widths = vec![Constraint::Length(10)];
+		let mut column_widths = TABLE_COLUMN_WIDTHS.to_vec();
+		let mut header = vec![
+			"Interface",
+			"Rx/s",
+			"Tx/s",
+			"Rx",
+			"Tx",
+			"Errors",
+			"State",
+			"MTU",
+			"Speed",
+			"Signal",
+			"Addresses",
+		];
+		// The month's usage goes after the totals since boot when it's being tracked.
+		let month = Local::now().format("%Y-%m").to_string();
+		if self.usage.is_some() {
+			column_widths.insert(4, 9);
+			header.insert(5, "Month");
+		}
+		for column_width in column_widths.iter() {
+			if width < column_width + 1 {
+				break;
+			}
+			widths.push(Constraint::Length(*column_width));
+			width -= column_width + 1;
+		}
+		if widths.len() > column_widths.len() && width > 0 {
+			widths.push(Constraint::Length(width));
+		}
+
+		let rate = |bytes: Option<u64>| {
+			bytes
+				.map(|bytes| self.units.format(bytes))
+				.unwrap_or_default()
+		};
+		Table::new(
+			header.iter(),
+			self.interfaces.iter().map(|(name, interface)| {
+				let info = &interface.info;
+				let style = if interface.errors_increased {
+					self.colorscheme.temp_high
+				} else {
+					self.colorscheme.text
+				};
+				let style = if *name == self.interface {
+					style.modifier(Modifier::BOLD)
+				} else {
+					style
+				};
+				let mut row = vec![
+					name.clone(),
+					rate(interface.bytes_recv),
+					rate(interface.bytes_sent),
+					self.units.format(interface.total_bytes_recv),
+					self.units.format(interface.total_bytes_sent),
+					interface.total_errors.to_string(),
+					info.state.clone(),
+					info.mtu.map(|mtu| mtu.to_string()).unwrap_or_default(),
+					info.speed_mbps
+						.map(|speed| {
+							if speed >= 1000 {
+								format!("{}G", speed / 1000)
+							} else {
+								format!("{}M", speed)
+							}
+						})
+						.unwrap_or_default(),
+					info.wireless
+						.as_ref()
+						.map(format_signal)
+						.unwrap_or_default(),
+					info.addresses
+						.iter()
+						.map(|address| address.to_string())
+						.collect::<Vec<_>>()
+						.join(" "),
+				];
+				if let Some(usage) = self.usage.as_ref() {
+					let usage = usage.month_usage(&month, |usage_name| usage_name == name);
+					row.insert(5, self.units.format(usage.total()));
+				}
+				Row::StyledData(row.into_iter(), style)
+			}),
+		)
+		.block(block::new(self.colorscheme, &self.title))
+		.header_style(self.colorscheme.text.modifier(Modifier::BOLD))
+		.widths(&widths)
+		.column_spacing(1)
+		.header_gap(0)
+		.render(area, buf);
+	}
+}
+
+impl Widget for &NetWidget<'_> {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		if area.height < 3 || area.width < 3 {
+			return;
+		}
+
+		if self.view == NetView::Table {
+			self.render_table(area, buf);
+			self.render_usage(area, buf);
+			return;
+		}
+
+		block::new(self.colorscheme, &self.title).render(area, buf);
+		self.render_usage(area, buf);
+
+		let mut inner = Rect {
+			x: area.x + 1,
+			y: area.y + 1,
+			width: area.width - 2,
+			height: area.height - 2,
+		};
+
+		// Wireless interfaces get a line each at the bottom when there's room for them.
+		let wireless: Vec<(&String, &Wireless)> = self
+			.interfaces
+			.iter()
+			.filter(|(name, _interface)| self.is_interface_counted(name))
+			.filter_map(|(name, interface)| {
+				interface
+					.info
+					.wireless
+					.as_ref()
+					.map(|wireless| (name, wireless))
+			})
+			.collect();
+		if !wireless.is_empty() && inner.height >= 5 + wireless.len() as u16 {
+			inner.height -= wireless.len() as u16;
+			for (i, (name, wireless)) in wireless.into_iter().enumerate() {
+				self.render_wireless(
+					Rect {
+						x: inner.x,
+						y: inner.bottom() + i as u16,
+						width: inner.width,
+						height: 1,
+					},
+					buf,
+					name,
+					wireless,
+				);
+			}
+		}
+
+		let top_half = Rect {
+			x: inner.x,
+			y: inner.y,
+			width: inner.width,
+			height: (inner.height / 2),
+		};
+
+		let bottom_half = Rect {
+			x: inner.x,
+			y: inner.y + (inner.height / 2),
+			width: inner.width,
+			height: (inner.height / 2),
+		};
+
+		// Each half needs room for its total and rate, while `render_traffic` leaves out the lines
+		// below them that don't fit in its half.
+		if inner.height < 3 {
+			return;
+		}
+
+		self.render_traffic(
+			top_half,
+			buf,
+			Traffic {
+				label: "Rx",
+				total_bytes: self.total_bytes_recv,
+				bytes: &self.bytes_recv,
+				packets: self.packets_recv,
+				previous_packets: self.previous_packets.map(|(recv, _sent)| recv),
+			},
+		);
+
+		if inner.height < 5 {
+			return;
+		}
+
+		self.render_traffic(
+			bottom_half,
+			buf,
+			Traffic {
+				label: "Tx",
+				total_bytes: self.total_bytes_sent,
+				bytes: &self.bytes_sent,
+				packets: self.packets_sent,
+				previous_packets: self.previous_packets.map(|(_recv, sent)| sent),
+			},
+		);
+	}
+}
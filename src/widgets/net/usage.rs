@@ -0,0 +1,315 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use serde::{Deserialize, Serialize};
+
+const BOOT_ID: &str = "/proc/sys/kernel/random/boot_id";
+
+// Daily and monthly usage older than these is dropped so that the file doesn't grow forever.
+const DAYS_KEPT: usize = 62;
+const MONTHS_KEPT: usize = 24;
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq)]
+pub struct Usage {
+	pub recv: u64,
+	pub sent: u64,
+}
+
+impl Usage {
+	pub fn total(self) -> u64 {
+		self.recv + self.sent
+	}
+
+	fn add(&mut self, other: Usage) {
+		self.recv += other.recv;
+		self.sent += other.sent;
+	}
+}
+
+/// A monthly data allowance and how full it gets before we warn about it.
+#[derive(Clone, Copy)]
+pub struct Quota {
+	pub bytes: u64,
+	pub warning_percent: f64,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct UsageFile {
+	// Identifies the boot that `counters` are from, since the kernel's counters start over on boot.
+	boot_id: Option<String>,
+	// The kernel's counters of each interface as of the last time usage was recorded.
+	counters: BTreeMap<String, Usage>,
+	// Usage of each interface by day, e.g. "2020-05-31", and by month, e.g. "2020-05".
+	days: BTreeMap<String, BTreeMap<String, Usage>>,
+	months: BTreeMap<String, BTreeMap<String, Usage>>,
+}
+
+/// Accumulates the traffic of each interface into daily and monthly totals that are kept across
+/// restarts of ytop.
+pub struct UsageTracker {
+	path: PathBuf,
+	file: UsageFile,
+	// The day that usage was last recorded on, which is where traffic gets added when merging.
+	day: Option<String>,
+	pub quota: Option<Quota>,
+}
+
+// How much a counter went up since it was last seen.
+fn counter_delta(previous: Option<u64>, current: u64) -> u64 {
+	match previous {
+		// The counter wrapped or the interface was recreated, so everything it has counted is new.
+		Some(previous) if current < previous => current,
+		Some(previous) => current - previous,
+		// The interface showed up since the last update.
+		None => current,
+	}
+}
+
+pub fn read_boot_id() -> Option<String> {
+	fs::read_to_string(BOOT_ID)
+		.ok()
+		.map(|boot_id| boot_id.trim().to_string())
+}
+
+fn read_usage_file(path: &Path) -> Option<UsageFile> {
+	fs::read_to_string(path)
+		.ok()
+		.and_then(|contents| serde_json::from_str(&contents).ok())
+}
+
+impl UsageFile {
+	// Adds the difference between `counters` and the ones in the file to `day` and its month, and
+	// keeps `counters` for next time.
+	fn record(&mut self, counters: BTreeMap<String, Usage>, boot_id: &str, day: &str) {
+		let first_run = self.boot_id.is_none();
+		if self.boot_id != Some(boot_id.to_string()) {
+			// After a reboot, everything the kernel has counted is new. The traffic between the
+			// last update and the reboot is lost.
+			self.counters.clear();
+			self.boot_id = Some(boot_id.to_string());
+		}
+		if !first_run {
+			let month = &day[..usize::min(7, day.len())];
+			for (name, current) in counters.iter() {
+				let previous = self.counters.get(name);
+				let delta = Usage {
+					recv: counter_delta(previous.map(|usage| usage.recv), current.recv),
+					sent: counter_delta(previous.map(|usage| usage.sent), current.sent),
+				};
+				self.days
+					.entry(day.to_string())
+					.or_default()
+					.entry(name.clone())
+					.or_default()
+					.add(delta);
+				self.months
+					.entry(month.to_string())
+					.or_default()
+					.entry(name.clone())
+					.or_default()
+					.add(delta);
+			}
+		}
+		// The first run only takes note of the counters since we don't know when the traffic the
+		// kernel has already counted happened.
+		self.counters = counters;
+
+		while self.days.len() > DAYS_KEPT {
+			let oldest = self.days.keys().next().cloned().unwrap();
+			self.days.remove(&oldest);
+		}
+		while self.months.len() > MONTHS_KEPT {
+			let oldest = self.months.keys().next().cloned().unwrap();
+			self.months.remove(&oldest);
+		}
+	}
+}
+
+impl UsageTracker {
+	/// Loads the usage saved at `path`, starting over if it's missing or unreadable.
+	pub fn load(path: PathBuf, quota: Option<Quota>) -> UsageTracker {
+		let file = read_usage_file(&path).unwrap_or_default();
+		UsageTracker {
+			path,
+			file,
+			day: None,
+			quota,
+		}
+	}
+
+	/// Saves the usage to the file it was loaded from, keeping what other instances of ytop have
+	/// saved there since.
+	pub fn save(&mut self) -> io::Result<()> {
+		if let Some(parent) = self.path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		// Every instance counts the same kernel counters, so the traffic since the counters in the
+		// saved file is added to it rather than replacing it with our totals. Nothing gets counted
+		// twice, even if another instance saves in between.
+		if let (Some(saved), Some(boot_id), Some(day)) = (
+			read_usage_file(&self.path),
+			self.file.boot_id.clone(),
+			self.day.as_ref(),
+		) {
+			let mut merged = saved;
+			if merged.boot_id.is_some() {
+				merged.record(self.file.counters.clone(), &boot_id, day);
+				self.file = merged;
+			}
+		}
+		// Written to a temporary file first so that a crash can't leave a truncated file behind.
+		let temp_path = self
+			.path
+			.with_extension(format!("json.{}.tmp", process::id()));
+		fs::write(&temp_path, serde_json::to_string(&self.file)?)?;
+		fs::rename(&temp_path, &self.path)
+	}
+
+	/// Adds the traffic since the counters were last recorded to `day`, e.g. "2020-05-31", and its
+	/// month. `counters` are the kernel's counters of each interface, which count up from boot.
+	pub fn record(&mut self, counters: BTreeMap<String, Usage>, boot_id: &str, day: &str) {
+		self.file.record(counters, boot_id, day);
+		self.day = Some(day.to_string());
+	}
+
+	/// Whether usage was last recorded on a different day than `day`, e.g. "2020-05-31".
+	pub fn is_new_day(&self, day: &str) -> bool {
+		match &self.day {
+			Some(recorded_day) => recorded_day != day,
+			None => false,
+		}
+	}
+
+	/// Usage of the interfaces that match `filter` on `day`, e.g. "2020-05-31".
+	pub fn day_usage<F: Fn(&str) -> bool>(&self, day: &str, filter: F) -> Usage {
+		sum_usage(self.file.days.get(day), filter)
+	}
+
+	/// Usage of the interfaces that match `filter` in `month`, e.g. "2020-05".
+	pub fn month_usage<F: Fn(&str) -> bool>(&self, month: &str, filter: F) -> Usage {
+		sum_usage(self.file.months.get(month), filter)
+	}
+}
+
+fn sum_usage<F: Fn(&str) -> bool>(usage: Option<&BTreeMap<String, Usage>>, filter: F) -> Usage {
+	let mut total = Usage::default();
+	for (name, usage) in usage.into_iter().flatten() {
+		if filter(name) {
+			total.add(*usage);
+		}
+	}
+	total
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn counters(recv: u64, sent: u64) -> BTreeMap<String, Usage> {
+		let mut counters = BTreeMap::new();
+		counters.insert("eth0".to_string(), Usage { recv, sent });
+		counters
+	}
+
+	fn tracker() -> UsageTracker {
+		UsageTracker::load(PathBuf::from("/nonexistent/net_usage.json"), None)
+	}
+
+	#[test]
+	fn it_accumulates_usage() {
+		let mut tracker = tracker();
+		// Traffic from before the first run isn't counted.
+		tracker.record(counters(1000, 500), "boot", "2020-05-31");
+		tracker.record(counters(1300, 600), "boot", "2020-05-31");
+		tracker.record(counters(1400, 700), "boot", "2020-06-01");
+
+		let all = |_name: &str| true;
+		assert_eq!(
+			tracker.day_usage("2020-05-31", all),
+			Usage {
+				recv: 300,
+				sent: 100,
+			}
+		);
+		assert_eq!(tracker.month_usage("2020-06", all).total(), 200);
+		assert_eq!(
+			tracker
+				.month_usage("2020-06", |name| name != "eth0")
+				.total(),
+			0
+		);
+	}
+
+	#[test]
+	fn it_handles_reboots_and_wraps() {
+		let mut tracker = tracker();
+		tracker.record(counters(1000, 1000), "boot", "2020-05-31");
+		// The counters start over on reboot.
+		tracker.record(counters(50, 40), "reboot", "2020-05-31");
+		assert_eq!(tracker.month_usage("2020-05", |_| true).total(), 90);
+		// A counter that goes backwards wrapped.
+		tracker.record(counters(10, 60), "reboot", "2020-05-31");
+		assert_eq!(
+			tracker.month_usage("2020-05", |_| true),
+			Usage { recv: 60, sent: 60 }
+		);
+	}
+
+	#[test]
+	fn it_merges_with_other_instances() {
+		let path = std::env::temp_dir().join(format!("ytop-net-usage-{}.json", process::id()));
+		let _ = fs::remove_file(&path);
+		let mut first = UsageTracker::load(path.clone(), None);
+		first.record(counters(1000, 1000), "boot", "2020-05-31");
+		first.save().unwrap();
+
+		let mut second = UsageTracker::load(path.clone(), None);
+		first.record(counters(1100, 1000), "boot", "2020-05-31");
+		second.record(counters(1300, 1000), "boot", "2020-05-31");
+		first.save().unwrap();
+		second.save().unwrap();
+		// Both counted the same traffic, which is only added once.
+		assert_eq!(second.month_usage("2020-05", |_| true).total(), 300);
+
+		let mut first = UsageTracker::load(path.clone(), None);
+		first.record(counters(1400, 1000), "boot", "2020-05-31");
+		first.save().unwrap();
+		assert_eq!(first.month_usage("2020-05", |_| true).total(), 400);
+		fs::remove_file(&path).unwrap();
+	}
+
+	#[test]
+	fn it_keeps_traffic_on_the_day_it_was_saved_before() {
+		let path = std::env::temp_dir().join(format!("ytop-net-days-{}.json", process::id()));
+		let _ = fs::remove_file(&path);
+		let mut tracker = UsageTracker::load(path.clone(), None);
+		tracker.record(counters(1000, 1000), "boot", "2020-05-31");
+		tracker.save().unwrap();
+		tracker.record(counters(1100, 1000), "boot", "2020-05-31");
+		// The net widget saves when the day changes, before recording the new day.
+		assert!(tracker.is_new_day("2020-06-01"));
+		tracker.save().unwrap();
+		tracker.record(counters(1300, 1000), "boot", "2020-06-01");
+		tracker.save().unwrap();
+		assert_eq!(tracker.day_usage("2020-05-31", |_| true).total(), 100);
+		assert_eq!(tracker.day_usage("2020-06-01", |_| true).total(), 200);
+		fs::remove_file(&path).unwrap();
+	}
+
+	#[test]
+	fn it_drops_old_usage() {
+		let mut tracker = tracker();
+		tracker.record(counters(0, 0), "boot", "2018-01-01");
+		for month in 1..=36 {
+			let day = format!("{}-{:02}-01", 2018 + (month - 1) / 12, (month - 1) % 12 + 1);
+			tracker.record(counters(month, 0), "boot", &day);
+		}
+		assert_eq!(tracker.file.days.len(), 36);
+		assert_eq!(tracker.file.months.len(), MONTHS_KEPT);
+		assert_eq!(tracker.month_usage("2018-12", |_| true).total(), 0);
+		assert_eq!(tracker.month_usage("2020-12", |_| true).total(), 1);
+	}
+}
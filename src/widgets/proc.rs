@@ -15,10 +15,10 @@ use tui::widgets::{Row, Table, Widget};
 
 use crate::colorscheme::Colorscheme;
 use crate::update::UpdatableWidget;
-use crate::widgets::block;
+use crate::widgets::{block, ScrollableTable};
 
-const UP_ARROW: &str = "▲";
-const DOWN_ARROW: &str = "▼";
+pub const UP_ARROW: &str = "▲";
+pub const DOWN_ARROW: &str = "▼";
 
 #[derive(PartialEq)]
 enum SortMethod {
@@ -35,7 +35,7 @@ impl Default for SortMethod {
 }
 
 #[derive(PartialEq, Clone, Copy)]
-enum SortDirection {
+pub enum SortDirection {
 	Up,
 	Down,
 }
@@ -137,42 +137,6 @@ impl ProcWidget<'_> {
 		self.scrolled = true;
 	}
 
-	pub fn scroll_up(&mut self) {
-		self.scroll_count(-1);
-	}
-
-	pub fn scroll_down(&mut self) {
-		self.scroll_count(1);
-	}
-
-	pub fn scroll_top(&mut self) {
-		self.scroll_to(0);
-	}
-
-	pub fn scroll_bottom(&mut self) {
-		self.scroll_to(if self.grouping {
-			self.grouped_procs.len()
-		} else {
-			self.procs.len()
-		});
-	}
-
-	pub fn scroll_half_page_down(&mut self) {
-		self.scroll_count(self.view_height as isize / 2);
-	}
-
-	pub fn scroll_half_page_up(&mut self) {
-		self.scroll_count(-(self.view_height as isize / 2));
-	}
-
-	pub fn scroll_full_page_down(&mut self) {
-		self.scroll_count(self.view_height as isize);
-	}
-
-	pub fn scroll_full_page_up(&mut self) {
-		self.scroll_count(-(self.view_height as isize));
-	}
-
 	pub fn toggle_grouping(&mut self) {
 		self.grouping = !self.grouping;
 		self.selected_proc = None;
@@ -212,6 +176,44 @@ impl ProcWidget<'_> {
 	}
 }
 
+impl ScrollableTable for ProcWidget<'_> {
+	fn scroll_up(&mut self) {
+		self.scroll_count(-1);
+	}
+
+	fn scroll_down(&mut self) {
+		self.scroll_count(1);
+	}
+
+	fn scroll_top(&mut self) {
+		self.scroll_to(0);
+	}
+
+	fn scroll_bottom(&mut self) {
+		self.scroll_to(if self.grouping {
+			self.grouped_procs.len()
+		} else {
+			self.procs.len()
+		});
+	}
+
+	fn scroll_half_page_down(&mut self) {
+		self.scroll_count(self.view_height as isize / 2);
+	}
+
+	fn scroll_half_page_up(&mut self) {
+		self.scroll_count(-(self.view_height as isize / 2));
+	}
+
+	fn scroll_full_page_down(&mut self) {
+		self.scroll_count(self.view_height as isize);
+	}
+
+	fn scroll_full_page_up(&mut self) {
+		self.scroll_count(-(self.view_height as isize));
+	}
+}
+
 impl UpdatableWidget for ProcWidget<'_> {
 	fn update(&mut self) {
 		self.process_collector.update().unwrap();
@@ -351,7 +353,7 @@ impl Widget for &mut ProcWidget<'_> {
 
 		if self.scrolled {
 			self.scrolled = false;
-			if self.selected_row > inner.height as usize + self.view_offset - 2 {
+			if self.selected_row > (inner.height as usize + self.view_offset).saturating_sub(2) {
 				self.view_offset = self.selected_row + 2 - inner.height as usize;
 			} else if self.selected_row < self.view_offset {
 				self.view_offset = self.selected_row;
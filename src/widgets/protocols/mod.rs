@@ -0,0 +1,212 @@
+mod snmp;
+
+use std::path::PathBuf;
+
+use num_rational::Ratio;
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::widgets::Widget;
+
+use crate::colorscheme::Colorscheme;
+use crate::history::History;
+use crate::sparkline::{RenderDirection, Sparkline};
+use crate::update::UpdatableWidget;
+use crate::widgets::block;
+
+use self::snmp::Counters;
+
+const PROC_ROOT: &str = "/proc";
+
+// Width of the label and rate shown to the left of each sparkline.
+const TEXT_WIDTH: u16 = 32;
+
+/// A counter that's shown as a rate, which is highlighted when it's a sign of trouble.
+struct Metric {
+	label: &'static str,
+	section: &'static str,
+	name: &'static str,
+	is_problem: bool,
+}
+
+const METRICS: [Metric; 8] = [
+	Metric {
+		label: "TCP retransmits",
+		section: "Tcp",
+		name: "RetransSegs",
+		is_problem: false,
+	},
+	Metric {
+		label: "TCP active opens",
+		section: "Tcp",
+		name: "ActiveOpens",
+		is_problem: false,
+	},
+	Metric {
+		label: "TCP passive opens",
+		section: "Tcp",
+		name: "PassiveOpens",
+		is_problem: false,
+	},
+	Metric {
+		label: "TCP resets sent",
+		section: "Tcp",
+		name: "OutRsts",
+		is_problem: false,
+	},
+	Metric {
+		label: "Listen overflows",
+		section: "TcpExt",
+		name: "ListenOverflows",
+		is_problem: true,
+	},
+	Metric {
+		label: "UDP recv errors",
+		section: "Udp",
+		name: "InErrors",
+		is_problem: true,
+	},
+	Metric {
+		label: "UDP rcvbuf drops",
+		section: "Udp",
+		name: "RcvbufErrors",
+		is_problem: true,
+	},
+	Metric {
+		label: "UDP sndbuf drops",
+		section: "Udp",
+		name: "SndbufErrors",
+		is_problem: true,
+	},
+];
+
+pub struct ProtocolsWidget<'a> {
+	title: String,
+	update_interval: Ratio<u64>,
+	colorscheme: &'a Colorscheme,
+
+	proc_root: PathBuf,
+
+	counters: Option<Counters>,
+	// Per second rates of each of `METRICS`.
+	rates: Vec<History<u64>>,
+	// Share of the TCP segments sent in the last update that were retransmissions.
+	retransmit_percent: Option<f64>,
+}
+
+impl ProtocolsWidget<'_> {
+	pub fn new(colorscheme: &Colorscheme) -> ProtocolsWidget {
+		ProtocolsWidget {
+			title: " Network Protocols ".to_string(),
+			update_interval: Ratio::from_integer(1),
+			colorscheme,
+
+			proc_root: PathBuf::from(PROC_ROOT),
+
+			counters: None,
+			rates: METRICS.iter().map(|_| History::default()).collect(),
+			retransmit_percent: None,
+		}
+	}
+
+	/// The height that fits a line for each metric.
+	pub fn get_height(&self) -> u16 {
+		METRICS.len() as u16 + 2
+	}
+}
+
+// How much a counter went up between two readings, which is 0 while it's unknown.
+fn counter_delta(previous: &Counters, current: &Counters, section: &str, name: &str) -> u64 {
+	let key = (section.to_string(), name.to_string());
+	match (previous.get(&key), current.get(&key)) {
+		(Some(previous), Some(current)) if current > previous => (current - previous) as u64,
+		_ => 0,
+	}
+}
+
+impl UpdatableWidget for ProtocolsWidget<'_> {
+	fn update(&mut self) {
+		let counters = snmp::read_counters(&self.proc_root);
+
+		if let Some(previous) = self.counters.as_ref() {
+			for (metric, rates) in METRICS.iter().zip(self.rates.iter_mut()) {
+				rates.push(counter_delta(
+					previous,
+					&counters,
+					metric.section,
+					metric.name,
+				));
+			}
+			let sent = counter_delta(previous, &counters, "Tcp", "OutSegs");
+			let retransmitted = counter_delta(previous, &counters, "Tcp", "RetransSegs");
+			self.retransmit_percent = if sent > 0 {
+				Some(retransmitted as f64 / sent as f64 * 100.0)
+			} else {
+				None
+			};
+		} else {
+			for rates in self.rates.iter_mut() {
+				rates.push(0);
+			}
+		}
+
+		self.counters = Some(counters);
+	}
+
+	fn get_update_interval(&self) -> Ratio<u64> {
+		self.update_interval
+	}
+}
+
+impl Widget for &ProtocolsWidget<'_> {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		block::new(self.colorscheme, &self.title).render(area, buf);
+
+		let inner = Rect {
+			x: area.x + 1,
+			y: area.y + 1,
+			width: area.width.saturating_sub(2),
+			height: area.height.saturating_sub(2),
+		};
+
+		for (i, (metric, rates)) in METRICS.iter().zip(self.rates.iter()).enumerate() {
+			if i as u16 >= inner.height {
+				break;
+			}
+			let y = inner.y + i as u16;
+			let rate = rates.last().cloned().unwrap_or_default();
+
+			let mut text = format!("{:<18} {}/s", metric.label, rate);
+			if let (0, Some(percent)) = (i, self.retransmit_percent) {
+				text = format!("{} ({:.1}%)", text, percent);
+			}
+			let style = if metric.is_problem && rate > 0 {
+				self.colorscheme.temp_high
+			} else {
+				self.colorscheme.text
+			};
+			buf.set_stringn(inner.x, y, text, inner.width as usize, style);
+
+			if inner.width <= TEXT_WIDTH {
+				continue;
+			}
+			let sparkline_area = Rect {
+				x: inner.x + TEXT_WIDTH,
+				y,
+				width: inner.width - TEXT_WIDTH,
+				height: 1,
+			};
+			let data = rates.as_slice();
+			let visible_data = &data[data.len().saturating_sub(sparkline_area.width as usize)..];
+			Sparkline::default()
+				.data(data)
+				.direction(RenderDirection::RTL)
+				.max(visible_data.iter().max().cloned().unwrap_or_default())
+				.style(if metric.is_problem {
+					self.colorscheme.temp_high
+				} else {
+					self.colorscheme.net_bars
+				})
+				.render(sparkline_area, buf);
+		}
+	}
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Protocol counters keyed by section and name, e.g. `("Tcp", "RetransSegs")`.
+pub type Counters = HashMap<(String, String), i64>;
+
+/// Parses /proc/net/snmp or /proc/net/netstat, where each section is a line of names followed by a
+/// line of values, e.g.:
+/// Tcp: RtoAlgorithm RtoMin RtoMax MaxConn ActiveOpens ...
+/// Tcp: 1 200 120000 -1 26 ...
+pub fn parse_counters(contents: &str, counters: &mut Counters) {
+	let mut lines = contents.lines();
+	while let (Some(names), Some(values)) = (lines.next(), lines.next()) {
+		let mut names = names.split_whitespace();
+		let mut values = values.split_whitespace();
+		let section = match (names.next(), values.next()) {
+			(Some(section), Some(value_section)) if section == value_section => {
+				section.trim_end_matches(':')
+			}
+			// Out of step, which the kernel doesn't do.
+			_ => return,
+		};
+		for (name, value) in names.zip(values) {
+			if let Ok(value) = value.parse() {
+				counters.insert((section.to_string(), name.to_string()), value);
+			}
+		}
+	}
+}
+
+/// Reads the counters from `<proc_root>/net/snmp` and `<proc_root>/net/netstat`, skipping files
+/// that can't be read.
+pub fn read_counters(proc_root: &Path) -> Counters {
+	let mut counters = Counters::new();
+	for file in ["snmp", "netstat"].iter() {
+		if let Ok(contents) = fs::read_to_string(proc_root.join("net").join(file)) {
+			parse_counters(&contents, &mut counters);
+		}
+	}
+	counters
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn counter(counters: &Counters, section: &str, name: &str) -> Option<i64> {
+		counters
+			.get(&(section.to_string(), name.to_string()))
+			.cloned()
+	}
+
+	#[test]
+	fn it_reads_counters() {
+		let counters =
+			read_counters(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/proc"));
+		assert_eq!(counter(&counters, "Tcp", "MaxConn"), Some(-1));
+		assert_eq!(counter(&counters, "Tcp", "RetransSegs"), Some(37));
+		assert_eq!(counter(&counters, "Udp", "RcvbufErrors"), Some(5));
+		assert_eq!(counter(&counters, "TcpExt", "ListenOverflows"), Some(2));
+		assert_eq!(counter(&counters, "IpExt", "InOctets"), Some(6_379_120));
+		assert_eq!(counter(&counters, "Tcp", "Missing"), None);
+	}
+}
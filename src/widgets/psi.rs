@@ -0,0 +1,289 @@
+use std::fs;
+use std::path::Path;
+
+use num_rational::Ratio;
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::symbols::Marker;
+use tui::widgets::{Axis, Chart, Dataset, GraphType, Widget};
+
+use crate::colorscheme::Colorscheme;
+use crate::history::{History, MAX_HORIZONTAL_SCALE};
+use crate::update::UpdatableWidget;
+use crate::widgets::{axis, block};
+
+const HORIZONTAL_SCALE_DELTA: u64 = 25;
+
+const PRESSURE_DIR: &str = "/proc/pressure";
+
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+struct PressureAverages {
+	avg10: f64,
+	avg60: f64,
+	avg300: f64,
+}
+
+// Parses a single line of a pressure file, e.g.:
+// some avg10=0.00 avg60=0.00 avg300=0.00 total=0
+fn parse_averages(line: &str) -> Option<PressureAverages> {
+	let mut averages = PressureAverages::default();
+	for field in line.split_whitespace().skip(1) {
+		let mut split = field.splitn(2, '=');
+		let (key, value) = (split.next()?, split.next()?);
+		match key {
+			"avg10" => averages.avg10 = value.parse().ok()?,
+			"avg60" => averages.avg60 = value.parse().ok()?,
+			"avg300" => averages.avg300 = value.parse().ok()?,
+			_ => {}
+		}
+	}
+
+	Some(averages)
+}
+
+// Parses a pressure file into its "some" and "full" averages. Kernels before 5.13 leave out the
+// "full" line for CPU pressure.
+fn parse_pressure(contents: &str) -> (PressureAverages, Option<PressureAverages>) {
+	let mut some = PressureAverages::default();
+	let mut full = None;
+	for line in contents.lines() {
+		if line.starts_with("some") {
+			some = parse_averages(line).unwrap_or_default();
+		} else if line.starts_with("full") {
+			full = parse_averages(line);
+		}
+	}
+	(some, full)
+}
+
+struct Pressure {
+	label: &'static str,
+	file: &'static str,
+
+	some: PressureAverages,
+	full: Option<PressureAverages>,
+
+	some_data: History<(f64, f64)>,
+	full_data: History<(f64, f64)>,
+}
+
+impl Pressure {
+	fn new(label: &'static str, file: &'static str, update_count: u64) -> Pressure {
+		let mut pressure = Pressure {
+			label,
+			file,
+
+			some: PressureAverages::default(),
+			full: None,
+
+			some_data: History::default(),
+			full_data: History::default(),
+		};
+		pressure.some_data.push((update_count as f64, 0.0));
+		pressure.full_data.push((update_count as f64, 0.0));
+		pressure
+	}
+}
+
+pub struct PsiWidget<'a> {
+	title: String,
+	update_interval: Ratio<u64>,
+	colorscheme: &'a Colorscheme,
+
+	horizontal_scale: u64,
+
+	update_count: u64,
+
+	pressures: Vec<Pressure>,
+}
+
+impl PsiWidget<'_> {
+	pub fn new(colorscheme: &Colorscheme, update_interval: Ratio<u64>) -> PsiWidget {
+		let update_count = 0;
+
+		PsiWidget {
+			title: " Pressure Stall ".to_string(),
+			update_interval,
+			colorscheme,
+
+			horizontal_scale: 100,
+
+			update_count,
+
+			pressures: vec![
+				Pressure::new("CPU", "cpu", update_count),
+				Pressure::new("Mem", "memory", update_count),
+				Pressure::new("IO ", "io", update_count),
+			],
+		}
+	}
+
+	/// Returns whether the kernel exposes pressure stall information for CPU, memory, and IO. The
+	/// pressure files can exist and still fail to read if PSI was disabled at boot with `psi=0`.
+	pub fn is_supported() -> bool {
+		["cpu", "memory", "io"]
+			.iter()
+			.all(|name| fs::read_to_string(Path::new(PRESSURE_DIR).join(name)).is_ok())
+	}
+
+	pub fn scale_in(&mut self) {
+		if self.horizontal_scale > HORIZONTAL_SCALE_DELTA {
+			self.horizontal_scale -= HORIZONTAL_SCALE_DELTA;
+		}
+	}
+
+	pub fn scale_out(&mut self) {
+		if self.horizontal_scale + HORIZONTAL_SCALE_DELTA <= MAX_HORIZONTAL_SCALE {
+			self.horizontal_scale += HORIZONTAL_SCALE_DELTA;
+		}
+	}
+}
+
+impl UpdatableWidget for PsiWidget<'_> {
+	fn update(&mut self) {
+		self.update_count += 1;
+
+		for pressure in self.pressures.iter_mut() {
+			let contents =
+				fs::read_to_string(Path::new(PRESSURE_DIR).join(pressure.file)).unwrap_or_default();
+			let (some, full) = parse_pressure(&contents);
+			pressure.some = some;
+			pressure.full = full;
+
+			pressure
+				.some_data
+				.push((self.update_count as f64, pressure.some.avg10));
+			if let Some(full) = pressure.full {
+				pressure
+					.full_data
+					.push((self.update_count as f64, full.avg10));
+			}
+		}
+	}
+
+	fn get_update_interval(&self) -> Ratio<u64> {
+		self.update_interval
+	}
+}
+
+impl Widget for &PsiWidget<'_> {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		let lines_len = self.colorscheme.cpu_lines.len();
+
+		let mut datasets = Vec::new();
+		for (i, pressure) in self.pressures.iter().enumerate() {
+			datasets.push(
+				Dataset::default()
+					.marker(Marker::Braille)
+					.graph_type(GraphType::Line)
+					.style(self.colorscheme.cpu_lines[(i * 2) % lines_len])
+					.data(pressure.some_data.as_slice()),
+			);
+			if pressure.full.is_some() {
+				datasets.push(
+					Dataset::default()
+						.marker(Marker::Braille)
+						.graph_type(GraphType::Line)
+						.style(self.colorscheme.cpu_lines[(i * 2 + 1) % lines_len])
+						.data(pressure.full_data.as_slice()),
+				);
+			}
+		}
+
+		// PSI percentages are usually small, so we scale the y axis to the largest visible value
+		// in steps of 10% instead of always showing 0-100%.
+		let min_x = self.update_count as f64 - self.horizontal_scale as f64;
+		let max_y = self
+			.pressures
+			.iter()
+			.flat_map(|pressure| {
+				pressure
+					.some_data
+					.as_slice()
+					.iter()
+					.chain(pressure.full_data.as_slice())
+			})
+			.filter(|(x, _y)| *x >= min_x)
+			.fold(0.0, |acc: f64, (_x, y)| acc.max(*y));
+		let max_y = if max_y >= 100.0 {
+			100.0
+		} else {
+			f64::max(10.0, (max_y / 10.0).ceil() * 10.0)
+		};
+
+		let x_labels = axis::time_labels(self.horizontal_scale, self.update_interval);
+		let y_labels = axis::percent_labels(max_y);
+		let graph_area = axis::graph_area(area, &x_labels, &y_labels);
+
+		Chart::<String, String>::default()
+			.block(block::new(self.colorscheme, &self.title))
+			.x_axis(
+				Axis::default()
+					.bounds([min_x, self.update_count as f64 + 1.0])
+					.labels(&x_labels)
+					.style(self.colorscheme.borders)
+					.labels_style(self.colorscheme.text),
+			)
+			.y_axis(
+				Axis::default()
+					.bounds([0.0, max_y])
+					.labels(&y_labels)
+					.style(self.colorscheme.borders)
+					.labels_style(self.colorscheme.text),
+			)
+			.datasets(&datasets)
+			.render(area, buf);
+
+		let mut y = graph_area.y + 1;
+		for (i, pressure) in self.pressures.iter().enumerate() {
+			let mut lines = vec![("some", pressure.some, (i * 2) % lines_len)];
+			if let Some(full) = pressure.full {
+				lines.push(("full", full, (i * 2 + 1) % lines_len));
+			}
+			for (kind, averages, color) in lines {
+				if y >= graph_area.bottom() {
+					return;
+				}
+				buf.set_string(
+					graph_area.x + 1,
+					y,
+					format!(
+						"{} {} {:5.2} {:5.2} {:5.2}",
+						pressure.label, kind, averages.avg10, averages.avg60, averages.avg300,
+					),
+					self.colorscheme.cpu_lines[color],
+				);
+				y += 1;
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn it_parses_pressure() {
+		let (some, full) = parse_pressure(
+			"some avg10=1.50 avg60=0.75 avg300=0.20 total=123456\n\
+			 full avg10=0.50 avg60=0.25 avg300=0.05 total=45678\n",
+		);
+		assert_eq!(
+			some,
+			PressureAverages {
+				avg10: 1.5,
+				avg60: 0.75,
+				avg300: 0.2,
+			}
+		);
+		assert_eq!(full.map(|full| full.avg10), Some(0.5));
+	}
+
+	#[test]
+	fn it_parses_pressure_without_full() {
+		let (some, full) = parse_pressure("some avg10=2.00 avg60=1.00 avg300=0.50 total=99\n");
+		assert_eq!(some.avg10, 2.0);
+		assert_eq!(full, None);
+	}
+}
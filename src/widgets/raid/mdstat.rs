@@ -0,0 +1,238 @@
+use std::fs;
+use std::io;
+
+/// A device in an md array.
+#[derive(Debug, PartialEq)]
+pub struct MdMember {
+	pub name: String,
+	pub failed: bool,
+	pub spare: bool,
+}
+
+/// Progress of a resync, recovery, reshape, or check of an md array.
+#[derive(Debug, PartialEq)]
+pub struct SyncProgress {
+	pub action: String,
+	pub percent: f64,
+	/// The kernel's estimate of how long the rest of the sync will take.
+	pub finish_minutes: Option<f64>,
+	pub speed_kib: Option<u64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MdArray {
+	pub name: String,
+	pub active: bool,
+	/// e.g. `raid1`, which is only known for active arrays.
+	pub level: Option<String>,
+	pub members: Vec<MdMember>,
+	/// How many devices the array should have and how many of them are working.
+	pub devices: Option<(u32, u32)>,
+	pub sync: Option<SyncProgress>,
+}
+
+impl MdArray {
+	pub fn is_degraded(&self) -> bool {
+		let missing = match self.devices {
+			Some((wanted, working)) => working < wanted,
+			None => false,
+		};
+		missing || self.members.iter().any(|member| member.failed)
+	}
+}
+
+/// Parses the contents of /proc/mdstat, e.g.:
+/// md1 : active raid5 sdc1[3] sdb2[1] sda2[0](F)
+///       2095104 blocks super 1.2 level 5, 512k chunk, algorithm 2 [3/2] [_UU]
+///       [=>...................]  recovery =  8.5% (89472/1047552) finish=1.2min speed=12345K/sec
+pub fn parse_mdstat(contents: &str) -> Vec<MdArray> {
+	let mut arrays: Vec<MdArray> = Vec::new();
+	for line in contents.lines() {
+		if !line.starts_with(char::is_whitespace) {
+			if let Some(array) = parse_array_line(line) {
+				arrays.push(array);
+			}
+			continue;
+		}
+		let array = match arrays.last_mut() {
+			Some(array) => array,
+			None => continue,
+		};
+		if let Some(devices) = parse_devices(line) {
+			array.devices = Some(devices);
+		} else if let Some(sync) = parse_sync(line) {
+			array.sync = Some(sync);
+		}
+	}
+	arrays
+}
+
+pub fn read_mdstat() -> io::Result<Vec<MdArray>> {
+	Ok(parse_mdstat(&fs::read_to_string("/proc/mdstat")?))
+}
+
+// e.g. "md0 : active raid1 sdb1[1] sda1[0]" or "md2 : inactive sdf1[0](S)"
+fn parse_array_line(line: &str) -> Option<MdArray> {
+	let mut parts = line.splitn(2, " : ");
+	let name = parts.next()?.trim();
+	if !name.starts_with("md") {
+		return None;
+	}
+	let mut fields = parts.next()?.split_whitespace().peekable();
+	let active = fields.next()? == "active";
+	// Skip states like "(read-only)".
+	while let Some(field) = fields.peek() {
+		if !field.starts_with('(') {
+			break;
+		}
+		fields.next();
+	}
+	let level = if active {
+		fields.next().map(|level| level.to_string())
+	} else {
+		None
+	};
+	let members = fields.filter_map(parse_member).collect();
+	Some(MdArray {
+		name: name.to_string(),
+		active,
+		level,
+		members,
+		devices: None,
+		sync: None,
+	})
+}
+
+// e.g. "sdb1[1]" or "sdd1[4](F)"
+fn parse_member(field: &str) -> Option<MdMember> {
+	let index = field.find('[')?;
+	let flags = &field[field.find(']')? + 1..];
+	Some(MdMember {
+		name: field[..index].to_string(),
+		failed: flags.contains("(F)"),
+		spare: flags.contains("(S)"),
+	})
+}
+
+// Finds the "[3/2]" in the line after the array line.
+fn parse_devices(line: &str) -> Option<(u32, u32)> {
+	line.split_whitespace().find_map(|field| {
+		if !field.starts_with('[') || !field.ends_with(']') {
+			return None;
+		}
+		let mut counts = field[1..field.len() - 1].split('/');
+		let wanted = counts.next()?.parse().ok()?;
+		let working = counts.next()?.parse().ok()?;
+		Some((wanted, working))
+	})
+}
+
+// e.g. "[=>......]  recovery =  8.5% (89472/1047552) finish=1.2min speed=12345K/sec"
+fn parse_sync(line: &str) -> Option<SyncProgress> {
+	let mut fields = line
+		.split_whitespace()
+		.skip_while(|field| field.starts_with('['));
+	let action = fields.next()?;
+	if fields.next()? != "=" {
+		return None;
+	}
+	let percent = fields.next()?.trim_end_matches('%').parse().ok()?;
+	let mut sync = SyncProgress {
+		action: action.to_string(),
+		percent,
+		finish_minutes: None,
+		speed_kib: None,
+	};
+	for field in fields {
+		let mut parts = field.splitn(2, '=');
+		match (parts.next(), parts.next()) {
+			(Some("finish"), Some(value)) => {
+				sync.finish_minutes = value.trim_end_matches("min").parse().ok();
+			}
+			(Some("speed"), Some(value)) => {
+				sync.speed_kib = value.trim_end_matches("K/sec").parse().ok();
+			}
+			_ => {}
+		}
+	}
+	Some(sync)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const MDSTAT: &str = "Personalities : [raid1] [raid6] [raid5] [raid4]
+md1 : active raid5 sdc1[3] sdb2[1] sda2[0] sdd1[4](F)
+      2095104 blocks super 1.2 level 5, 512k chunk, algorithm 2 [3/2] [UU_]
+      [=>...................]  recovery =  8.5% (89472/1047552) finish=1.2min speed=12345K/sec
+
+md0 : active raid1 sdb1[1] sda1[0]
+      1048512 blocks super 1.2 [2/2] [UU]
+
+md2 : inactive sdf1[0](S)
+      1048576 blocks
+
+unused devices: <none>
+";
+
+	#[test]
+	fn it_parses_arrays() {
+		let arrays = parse_mdstat(MDSTAT);
+		assert_eq!(arrays.len(), 3);
+
+		let md1 = &arrays[0];
+		assert_eq!(md1.name, "md1");
+		assert!(md1.active);
+		assert_eq!(md1.level, Some("raid5".to_string()));
+		assert_eq!(md1.members.len(), 4);
+		assert_eq!(
+			md1.members[3],
+			MdMember {
+				name: "sdd1".to_string(),
+				failed: true,
+				spare: false,
+			}
+		);
+		assert_eq!(md1.devices, Some((3, 2)));
+		assert!(md1.is_degraded());
+		assert_eq!(
+			md1.sync,
+			Some(SyncProgress {
+				action: "recovery".to_string(),
+				percent: 8.5,
+				finish_minutes: Some(1.2),
+				speed_kib: Some(12345),
+			})
+		);
+
+		let md0 = &arrays[1];
+		assert_eq!(md0.devices, Some((2, 2)));
+		assert!(!md0.is_degraded());
+		assert_eq!(md0.sync, None);
+
+		let md2 = &arrays[2];
+		assert!(!md2.active);
+		assert_eq!(md2.level, None);
+		assert!(md2.members[0].spare);
+	}
+
+	#[test]
+	fn it_parses_delayed_resyncs() {
+		let arrays = parse_mdstat(
+			"md127 : active (auto-read-only) raid1 nvme1n1p2[1] nvme0n1p2[0]
+      976630464 blocks super 1.2 [2/2] [UU]
+      \tresync=DELAYED
+      bitmap: 0/8 pages [0KB], 65536KB chunk
+",
+		);
+		assert_eq!(arrays[0].level, Some("raid1".to_string()));
+		assert_eq!(arrays[0].members.len(), 2);
+		assert_eq!(arrays[0].sync, None);
+	}
+
+	#[test]
+	fn it_handles_no_arrays() {
+		assert!(parse_mdstat("Personalities : \nunused devices: <none>\n").is_empty());
+	}
+}
@@ -0,0 +1,138 @@
+mod mdstat;
+
+use num_rational::Ratio;
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::widgets::Widget;
+
+use crate::colorscheme::Colorscheme;
+use crate::units::Units;
+use crate::update::UpdatableWidget;
+use crate::widgets::{axis, block};
+
+use self::mdstat::MdArray;
+
+pub struct RaidWidget<'a> {
+	title: String,
+	update_interval: Ratio<u64>,
+	colorscheme: &'a Colorscheme,
+	units: Units,
+
+	arrays: Vec<MdArray>,
+}
+
+impl RaidWidget<'_> {
+	pub fn new(colorscheme: &Colorscheme, units: Units) -> RaidWidget {
+		RaidWidget {
+			title: " RAID ".to_string(),
+			update_interval: Ratio::from_integer(1),
+			colorscheme,
+			units,
+
+			arrays: mdstat::read_mdstat().unwrap_or_default(),
+		}
+	}
+
+	/// Whether there are any md arrays to show.
+	pub fn has_arrays() -> bool {
+		mdstat::read_mdstat()
+			.map(|arrays| !arrays.is_empty())
+			.unwrap_or(false)
+	}
+
+	/// The height that fits two lines for each array.
+	pub fn get_height(&self) -> u16 {
+		self.arrays.len() as u16 * 2 + 2
+	}
+}
+
+impl UpdatableWidget for RaidWidget<'_> {
+	fn update(&mut self) {
+		self.arrays = mdstat::read_mdstat().unwrap_or_default();
+	}
+
+	fn get_update_interval(&self) -> Ratio<u64> {
+		self.update_interval
+	}
+}
+
+// e.g. "md1 raid5 [3/2] degraded, recovery 8.5% 1m12s left"
+fn array_summary(units: Units, array: &MdArray) -> String {
+	let mut summary = array.name.clone();
+	if !array.active {
+		summary.push_str(" inactive");
+	}
+	if let Some(level) = &array.level {
+		summary = format!("{} {}", summary, level);
+	}
+	if let Some((wanted, working)) = array.devices {
+		summary = format!("{} [{}/{}]", summary, wanted, working);
+	}
+	if array.is_degraded() {
+		summary.push_str(" degraded");
+	}
+	if let Some(sync) = &array.sync {
+		summary = format!("{}, {} {:.1}%", summary, sync.action, sync.percent);
+		if let Some(minutes) = sync.finish_minutes {
+			summary = format!("{} {} left", summary, axis::format_duration(minutes * 60.0));
+		}
+		if let Some(speed) = sync.speed_kib {
+			summary = format!("{} at {}/s", summary, units.format(speed * 1024));
+		}
+	}
+	summary
+}
+
+// e.g. "sdc1 sdb2 sda2(F) sdd1(S)"
+fn members_summary(array: &MdArray) -> String {
+	array
+		.members
+		.iter()
+		.map(|member| {
+			if member.failed {
+				format!("{}(F)", member.name)
+			} else if member.spare {
+				format!("{}(S)", member.name)
+			} else {
+				member.name.clone()
+			}
+		})
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+impl Widget for &RaidWidget<'_> {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		block::new(self.colorscheme, &self.title).render(area, buf);
+
+		if area.height < 3 || area.width < 3 {
+			return;
+		}
+
+		let inner = Rect {
+			x: area.x + 1,
+			y: area.y + 1,
+			width: area.width - 2,
+			height: area.height - 2,
+		};
+
+		let lines = self.arrays.iter().flat_map(|array| {
+			let style = if array.is_degraded() {
+				self.colorscheme.temp_high
+			} else {
+				self.colorscheme.text
+			};
+			vec![
+				(array_summary(self.units, array), style),
+				(format!("  {}", members_summary(array)), style),
+			]
+		});
+		for (i, (text, style)) in lines.enumerate() {
+			let y = inner.y + i as u16;
+			if y >= inner.bottom() {
+				break;
+			}
+			buf.set_stringn(inner.x, y, text, inner.width as usize, style);
+		}
+	}
+}